@@ -48,7 +48,7 @@ pub extern "C" fn bond_calc_ytm(
     let date = create_date(year, month, day);
     let bond = unsafe { &*bond };
     bond.calc_ytm_with_price(dirty_price, date, None, None)
-        .unwrap()
+        .unwrap_or(f64::NAN)
 }
 
 #[unsafe(no_mangle)]
@@ -1,7 +1,15 @@
 from __future__ import annotations
 
 from .bond import Bond
-from .pybond import Future, Ib, Sse, get_version, update_info_from_wind_sql_df
+from .pybond import (
+    Future,
+    Ib,
+    NssCurve,
+    Sse,
+    YieldCurve,
+    get_version,
+    update_info_from_wind_sql_df,
+)
 from .pybond import TfEvaluator as _TfEvaluatorRS
 
 __version__ = get_version()
@@ -30,4 +38,13 @@ class TfEvaluator(_TfEvaluatorRS):
         return super().__new__(cls, future, bond, *args, **kwargs)
 
 
-__all__ = ["Bond", "Future", "Ib", "Sse", "TfEvaluator", "__version__"]
+__all__ = [
+    "Bond",
+    "Future",
+    "Ib",
+    "NssCurve",
+    "Sse",
+    "TfEvaluator",
+    "YieldCurve",
+    "__version__",
+]
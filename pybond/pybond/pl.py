@@ -1,8 +1,8 @@
 from __future__ import annotations
 
-from typing import TYPE_CHECKING
+from typing import TYPE_CHECKING, Literal
 
-from .pybond import Ib, Sse
+from .pybond import Ib, Sse, build_continuous_future
 
 if TYPE_CHECKING:
     from polars.type_aliases import IntoExpr
@@ -300,6 +300,38 @@ class TfEvaluators:
     def maturity_date(self):
         return self._call_plugin("bonds_maturity_date")
 
+    @property
+    def convexity(self):
+        """
+        Calculate bond convexity (凸性).
+
+        Returns:
+            Polars expression for convexity
+        """
+        return self._call_plugin("bonds_convexity")
+
+    @property
+    def dollar_duration(self):
+        """
+        Calculate dollar duration (金额久期).
+
+        Dollar duration = modified duration * dirty price
+
+        Returns:
+            Polars expression for dollar duration
+        """
+        return self._call_plugin("bonds_dollar_duration")
+
+    @property
+    def dv01(self):
+        """
+        Calculate DV01 (基点价值), the dirty price change per 100 face value for a 1bp yield move.
+
+        Returns:
+            Polars expression for DV01
+        """
+        return self._call_plugin("bonds_dv01")
+
     @property
     def neutral_net_basis_spread(self):
         """
@@ -430,6 +462,95 @@ class Bonds:
         """
         return self._evaluator(date=date, ytm=ytm).duration
 
+    def convexity(self, ytm: IntoExpr = "ytm", date: IntoExpr = "date"):
+        """
+        Calculate convexity (凸性).
+
+        Args:
+            ytm: Yield to maturity column expression
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for convexity
+        """
+        return self._evaluator(date=date, ytm=ytm).convexity
+
+    def dollar_duration(self, ytm: IntoExpr = "ytm", date: IntoExpr = "date"):
+        """
+        Calculate dollar duration (金额久期).
+
+        Args:
+            ytm: Yield to maturity column expression
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for dollar duration
+        """
+        return self._evaluator(date=date, ytm=ytm).dollar_duration
+
+    def dv01(self, ytm: IntoExpr = "ytm", date: IntoExpr = "date"):
+        """
+        Calculate DV01 (基点价值).
+
+        Args:
+            ytm: Yield to maturity column expression
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for DV01
+        """
+        return self._evaluator(date=date, ytm=ytm).dv01
+
+    def pv01(
+        self,
+        tenors: list[float],
+        zero_rates: list[float],
+        date: IntoExpr = "date",
+    ):
+        """
+        Calculate PV01 (曲线基点价值) with a zero curve built on the evaluation date.
+
+        Args:
+            tenors: Curve tenors in years
+            zero_rates: Zero rates of the tenors (annual compounding)
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for PV01
+        """
+        return self._evaluator(date=date)._call_plugin(
+            "bonds_pv01", tenors=list(tenors), zero_rates=list(zero_rates)
+        )
+
+    def key_rate_duration(
+        self,
+        key_tenors: list[float],
+        key_index: int,
+        tenors: list[float],
+        zero_rates: list[float],
+        date: IntoExpr = "date",
+    ):
+        """
+        Calculate key rate duration (关键期限久期) of `key_tenors[key_index]`.
+
+        Args:
+            key_tenors: Key tenors in years
+            key_index: Index of the key tenor to calculate
+            tenors: Curve tenors in years
+            zero_rates: Zero rates of the tenors (annual compounding)
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for key rate duration
+        """
+        return self._evaluator(date=date)._call_plugin(
+            "bonds_key_rate_duration",
+            key_tenors=list(key_tenors),
+            key_index=key_index,
+            tenors=list(tenors),
+            zero_rates=list(zero_rates),
+        )
+
     def remain_cp_num(self, date: IntoExpr = "date"):
         """
         Calculate remaining number of coupon payments (债券剩余付息次数).
@@ -442,6 +563,75 @@ class Bonds:
         """
         return self._evaluator(date=date).remain_cp_num
 
+    def g_spread(
+        self,
+        tenors: list[float],
+        yields: list[float],
+        ytm: IntoExpr = "ytm",
+        date: IntoExpr = "date",
+    ):
+        """
+        Calculate G-spread (相对国债收益率的利差).
+
+        Args:
+            tenors: Government curve tenors in years
+            yields: Government yields of the tenors
+            ytm: Yield to maturity column expression
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for G-spread
+        """
+        return self._evaluator(date=date, ytm=ytm)._call_plugin(
+            "bonds_g_spread", tenors=list(tenors), zero_rates=list(yields)
+        )
+
+    def i_spread(
+        self,
+        tenors: list[float],
+        swap_rates: list[float],
+        ytm: IntoExpr = "ytm",
+        date: IntoExpr = "date",
+    ):
+        """
+        Calculate I-spread (相对利率互换的利差).
+
+        Args:
+            tenors: Swap curve tenors in years
+            swap_rates: Swap rates of the tenors
+            ytm: Yield to maturity column expression
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for I-spread
+        """
+        return self._evaluator(date=date, ytm=ytm)._call_plugin(
+            "bonds_i_spread", tenors=list(tenors), zero_rates=list(swap_rates)
+        )
+
+    def z_spread(
+        self,
+        tenors: list[float],
+        zero_rates: list[float],
+        ytm: IntoExpr = "ytm",
+        date: IntoExpr = "date",
+    ):
+        """
+        Calculate Z-spread (零波动利差) over a zero curve built on the evaluation date.
+
+        Args:
+            tenors: Curve tenors in years
+            zero_rates: Zero rates of the tenors (annual compounding)
+            ytm: Yield to maturity column expression
+            date: Evaluation date column expression
+
+        Returns:
+            Polars expression for Z-spread
+        """
+        return self._evaluator(date=date, ytm=ytm)._call_plugin(
+            "bonds_z_spread", tenors=list(tenors), zero_rates=list(zero_rates)
+        )
+
     def calc_ytm_with_price(
         self,
         date: IntoExpr = "date",
@@ -567,3 +757,82 @@ def is_business_day(date: IntoExpr, market: str | Ib | Sse):
         symbol="calendar_is_business_day",
         is_elementwise=True,
     )
+
+
+def build_continuous(
+    df: pl.DataFrame,
+    rule: Literal["oi", "volume", "days"] = "oi",
+    adjust: Literal["ratio", "diff", "none"] = "ratio",
+    roll_days: int = 5,
+    date: str = "date",
+    code: str = "code",
+    price: str = "close",
+    oi: str = "oi",
+    volume: str = "volume",
+) -> tuple[pl.DataFrame, pl.DataFrame]:
+    """
+    Build a dominant-contract continuous future series (主力连续合约).
+
+    The roll signal is determined after the close, the new dominant contract is used
+    from the next trading day, and the dominant contract only rolls to later contracts.
+
+    Args:
+        df: Per-contract daily quotes in long format
+        rule: Roll rule, "oi" (open interest crossover), "volume" (volume crossover)
+            or "days" (roll_days trading days before the last trading date)
+        adjust: Back-adjustment mode, "ratio", "diff" or "none"
+        roll_days: Number of trading days before the last trading date, used by rule="days"
+        date: Date column name
+        code: Future code column name
+        price: Price column name
+        oi: Open interest column name, optional unless rule="oi"
+        volume: Volume column name, optional unless rule="volume"
+
+    Returns:
+        (continuous series with columns [date, code, raw_price, price],
+        roll schedule with columns [date, signal_date, from, to, from_price, to_price])
+    """
+    nan = pl.lit(float("nan"), dtype=pl.Float64)
+    df = df.select(
+        pl.col(date).cast(pl.Date),
+        pl.col(code).cast(pl.String),
+        pl.col(price).cast(pl.Float64).fill_null(float("nan")),
+        (pl.col(oi).cast(pl.Float64) if oi in df.columns else nan)
+        .fill_null(float("nan"))
+        .alias("__oi"),
+        (pl.col(volume).cast(pl.Float64) if volume in df.columns else nan)
+        .fill_null(float("nan"))
+        .alias("__volume"),
+    ).drop_nulls([date, code])
+    dates, codes, raw_prices, prices, rolls = build_continuous_future(
+        df[date].to_list(),
+        df[code].to_list(),
+        df[price].to_list(),
+        df["__oi"].to_list(),
+        df["__volume"].to_list(),
+        rule=rule,
+        adjust=adjust,
+        roll_days=roll_days,
+    )
+    series = pl.DataFrame(
+        {"date": dates, "code": codes, "raw_price": raw_prices, "price": prices},
+        schema={
+            "date": pl.Date,
+            "code": pl.String,
+            "raw_price": pl.Float64,
+            "price": pl.Float64,
+        },
+    )
+    schedule = pl.DataFrame(
+        rolls,
+        schema={
+            "date": pl.Date,
+            "signal_date": pl.Date,
+            "from": pl.String,
+            "to": pl.String,
+            "from_price": pl.Float64,
+            "to_price": pl.Float64,
+        },
+        orient="row",
+    )
+    return series, schedule
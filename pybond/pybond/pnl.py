@@ -216,7 +216,8 @@ def calc_trade_pnl(
     qty: 成交量, 正负号表⽰⽅向
     price: 成交价格
     close: 当前时间段的最新价格(净价)
-    multiplier: 合约乘数, 例如对于债券, 1000的成交对应1000w, 合约乘数应为100, 默认为1
+    multiplier: 合约乘数, 例如对于债券, 1000的成交对应1000w, 合约乘数应为100, 默认为1;
+        国债期货可使用Future(code).multiplier()
     fee: 交易费⽤
     费⽤设置说明:
         TradeFee: 每笔成交⽀付的费⽤
@@ -257,7 +258,7 @@ def trading_from_pos(
     pos: 当前时间的实际仓位, -1 ~ 1, 表⽰百分⽐
     open: 当前周期的开仓价格
     cash: 总资⾦, ⽤于计算实际开仓⼿数; 标量会广播, 序列则逐行使用
-    multiplier: 合约乘数, 默认为1
+    multiplier: 合约乘数, 默认为1, 国债期货可使用Future(code).multiplier()
     qty_tick: 最⼩开仓⼿数, 例如0.01, 0.1, 1, 100
     qty_round_mode: 手数取整模式, floor=按绝对手数向下取整, round=四舍五入
     stop_on_finish: 当前标的没有数据后是否平仓
@@ -1,5 +1,6 @@
 from datetime import date
 from pathlib import Path
+from typing import Literal
 
 class Bond:
     """A class representing a bond instrument."""
@@ -57,6 +58,21 @@ class Bond:
     def cp_rate(self) -> float:
         """票面利率, 浮动付息债券仅表示发行时票面利率"""
 
+    @property
+    def cp_rate_schedule(self) -> list[tuple[date, float]]:
+        """票面利率调整计划, 累进利率债券适用, 返回(生效日, 票面利率)列表"""
+
+    @property
+    def amortization(self) -> list[tuple[date, float]]:
+        """分期还本计划, 返回(还本日, 偿还本金占发行面值的比例)列表"""
+
+    @property
+    def embedded_options(self) -> list[tuple[str, date, float]]:
+        """行权条款, 返回(行权类型"Call"/"Put", 行权日, 行权价格)列表"""
+
+    def remaining_par(self, date: date) -> float:
+        """指定日期(含当日还本)之后的剩余本金"""
+
     @property
     def base_rate(self) -> float | None:
         """基准利率, 浮动付息债券适用"""
@@ -118,6 +134,16 @@ class Bond:
     ) -> list[date]:
         """剩余的付息日期列表"""
 
+    def cash_flows(
+        self, date: date | None = None
+    ) -> list[tuple[date, date, date, date, float, float]]:
+        """
+        现金流计划
+
+        返回(名义付息日, 实际支付日, 计息起始日, 计息截止日, 利息, 本金)列表,
+        传入date时仅返回该日之后的剩余现金流
+        """
+
     def calc_ytm_with_price(
         self,
         dirty_price: float,
@@ -127,6 +153,265 @@ class Bond:
     ) -> float:
         """通过债券全价计算ytm"""
 
+    def calc_ytc(self, dirty_price: float, date: date) -> float:
+        """通过债券全价计算到下一赎回日的收益率"""
+
+    def calc_ytp(self, dirty_price: float, date: date) -> float:
+        """通过债券全价计算到下一回售日的收益率"""
+
+    def calc_ytw(self, dirty_price: float, date: date) -> float:
+        """通过债券全价计算最差收益率(到期收益率与各赎回日收益率中的最小值)"""
+
+    def calc_ytc_duration(self, ytc: float, date: date) -> float:
+        """到下一赎回日的修正久期"""
+
+    def calc_ytp_duration(self, ytp: float, date: date) -> float:
+        """到下一回售日的修正久期"""
+
+    def calc_ytw_duration(self, dirty_price: float, date: date) -> float:
+        """最差收益率对应行权路径的修正久期"""
+
+    def calc_convexity(
+        self,
+        ytm: float,
+        date: date,
+        cp_dates: tuple[date, date] | None = None,
+        remain_cp_num: int | None = None,
+    ) -> float:
+        """计算凸性"""
+
+    def calc_dollar_duration(
+        self,
+        ytm: float,
+        date: date,
+        cp_dates: tuple[date, date] | None = None,
+        remain_cp_num: int | None = None,
+    ) -> float:
+        """计算美元久期(修正久期 * 全价)"""
+
+    def calc_dv01(
+        self,
+        ytm: float,
+        date: date,
+        cp_dates: tuple[date, date] | None = None,
+        remain_cp_num: int | None = None,
+    ) -> float:
+        """计算DV01, 收益率下降1bp时每百元面值全价的变动"""
+
+    def calc_dirty_price_with_curve(self, curve: YieldCurve, date: date) -> float:
+        """按即期收益率曲线计算全价"""
+
+    def calc_rich_cheap(
+        self, curve: YieldCurve, dirty_price: float, date: date
+    ) -> float:
+        """相对曲线的贵贱程度, 市场全价对应的到期收益率减去曲线定价对应的到期收益率, 为正表示偏便宜"""
+
+    def calc_g_spread(self, ytm: float, govt_curve: YieldCurve, date: date) -> float:
+        """计算G利差, 到期收益率减去按剩余期限插值的国债收益率"""
+
+    def calc_i_spread(self, ytm: float, swap_curve: YieldCurve, date: date) -> float:
+        """计算I利差, 到期收益率减去按剩余期限插值的利率互换利率"""
+
+    def calc_z_spread(self, dirty_price: float, curve: YieldCurve, date: date) -> float:
+        """计算Z利差, 即期利率曲线整体加上该利差后对剩余现金流折现等于全价"""
+
+    def floating_cash_flows(
+        self, date: date, fixings: list[tuple[date, float]], curve: YieldCurve
+    ) -> list[tuple[date, date, date, date, float, float]]:
+        """
+        浮动利率债券的现金流计划, 已重置的付息周期使用定盘利率, 之后的付息周期使用曲线推算的远期利率
+
+        Args:
+            date: 计算日
+            fixings: (定盘日, 基准利率)列表, 如DR007/Shibor/LPR
+            curve: 推算远期基准利率的曲线
+
+        Returns:
+            (名义付息日, 实际支付日, 计息起始日, 计息截止日, 利息, 本金)列表
+        """
+
+    def calc_frn_accrued_interest(
+        self, date: date, fixings: list[tuple[date, float]], curve: YieldCurve
+    ) -> float:
+        """浮动利率债券的应计利息, 按当期重置后的票面利息计提"""
+
+    def calc_frn_dirty_price(
+        self,
+        date: date,
+        fixings: list[tuple[date, float]],
+        curve: YieldCurve,
+        discount_margin: float,
+    ) -> float:
+        """通过贴现利差计算浮动利率债券全价"""
+
+    def calc_discount_margin(
+        self,
+        dirty_price: float,
+        date: date,
+        fixings: list[tuple[date, float]],
+        curve: YieldCurve,
+    ) -> float:
+        """通过浮动利率债券全价计算贴现利差"""
+
+    def calc_spread_duration(
+        self,
+        date: date,
+        fixings: list[tuple[date, float]],
+        curve: YieldCurve,
+        discount_margin: float,
+    ) -> float:
+        """利差久期, 贴现利差变动1bp时全价的相对变动"""
+
+    def calc_forward_dirty_price(
+        self,
+        dirty_price: float,
+        date: date,
+        forward_date: date,
+        repo_rate: float,
+        reinvest_rate: float | None = None,
+    ) -> float:
+        """通过全价计算远期全价, 期间现金流按再投资利率(默认为回购利率)再投资至远期结算日"""
+
+    def calc_forward_clean_price(
+        self,
+        dirty_price: float,
+        date: date,
+        forward_date: date,
+        repo_rate: float,
+        reinvest_rate: float | None = None,
+    ) -> float:
+        """通过全价计算远期净价"""
+
+    def calc_implied_repo(
+        self,
+        dirty_price: float,
+        date: date,
+        forward_date: date,
+        forward_dirty_price: float,
+        reinvest_rate: float | None = None,
+    ) -> float:
+        """通过全价和远期全价计算隐含回购利率, 未指定再投资利率时按隐含回购利率再投资"""
+
+    def calc_pv01(self, curve: YieldCurve, date: date) -> float:
+        """计算PV01, 即期收益率曲线整体下移1bp时每百元面值全价的变动"""
+
+    def calc_key_rate_durations(
+        self, curve: YieldCurve, date: date, key_tenors: list[float]
+    ) -> list[float]:
+        """计算关键期限久期, 返回与key_tenors一一对应的久期列表"""
+
+class YieldCurve:
+    """即期收益率曲线, 关键期限之间按插值方法插值, 两端水平外推"""
+
+    def __init__(
+        self,
+        ref_date: date,
+        tenors: list[float],
+        zero_rates: list[float],
+        interpolation: Literal[
+            "linear", "log_linear", "cubic", "monotone_convex"
+        ] = "linear",
+    ) -> None:
+        """
+        Args:
+            ref_date: 曲线基准日
+            tenors: 关键期限(年), 严格递增
+            zero_rates: 关键期限对应的即期利率(年复利)
+            interpolation: 插值方法
+        """
+
+    @staticmethod
+    def flat(ref_date: date, rate: float) -> YieldCurve:
+        """水平曲线"""
+
+    @staticmethod
+    def bootstrap(
+        ref_date: date,
+        bonds: list[Bond | str],
+        dirty_prices: list[float],
+        interpolation: Literal[
+            "linear", "log_linear", "cubic", "monotone_convex"
+        ] = "linear",
+    ) -> YieldCurve:
+        """由债券及其全价拔靴构造曲线, 以各债券的到期支付日作为关键期限"""
+
+    @staticmethod
+    def from_par_yields(
+        ref_date: date,
+        tenors: list[float],
+        par_yields: list[float],
+        inst_freq: int = 1,
+        interpolation: Literal[
+            "linear", "log_linear", "cubic", "monotone_convex"
+        ] = "linear",
+    ) -> YieldCurve:
+        """
+        由关键期限的平价收益率拔靴构造曲线
+
+        期限不超过一年按单利贴现债券处理, 超过一年按平价发行的附息债券处理
+        """
+
+    @property
+    def ref_date(self) -> date: ...
+    @property
+    def tenors(self) -> list[float]: ...
+    @property
+    def zero_rates(self) -> list[float]: ...
+    @property
+    def interpolation(self) -> str: ...
+    def shift(self, shift: float) -> YieldCurve:
+        """曲线平移, shift为平移的利率(如0.0001为1bp)"""
+
+    def zero_rate(self, t: float) -> float:
+        """期限为t年的即期利率"""
+
+    def discount_factor(self, date: date) -> float:
+        """指定日期的贴现因子"""
+
+    def forward_rate(self, start: date, end: date) -> float:
+        """两个日期之间的远期利率(单利)"""
+
+class NssCurve:
+    """Nelson-Siegel(-Svensson)参数化即期收益率曲线, 即期利率为年复利"""
+
+    @staticmethod
+    def fit(
+        date: date,
+        bonds: list[Bond | str],
+        ytms: list[float],
+        model: Literal["nelson_siegel", "svensson"] = "nelson_siegel",
+        objective: Literal["yield", "price"] = "yield",
+        duration_weighted: bool = False,
+    ) -> tuple[NssCurve, list[float], list[float]]:
+        """
+        用债券收益率拟合曲线
+
+        Args:
+            date: 计算日, 即曲线基准日
+            bonds: 债券或债券代码
+            ytms: 债券到期收益率
+            model: 曲线模型
+            objective: 按收益率误差或全价误差拟合
+            duration_weighted: 是否按久期加权(价格误差除以久期, 收益率误差乘以久期)
+
+        Returns:
+            (拟合曲线, 全价残差, 收益率残差), 残差为市场值减模型值
+        """
+
+    @property
+    def ref_date(self) -> date: ...
+    @property
+    def model(self) -> str: ...
+    @property
+    def params(self) -> list[float]:
+        """Nelson-Siegel为[β0, β1, β2, τ1], Svensson为[β0, β1, β2, β3, τ1, τ2]"""
+
+    def zero_rate(self, t: float) -> float:
+        """期限为t年的即期利率"""
+
+    def discount_factor(self, date: date) -> float:
+        """指定日期的贴现因子"""
+
 class Future:
     """A class representing a treasury futures contract."""
 
@@ -134,11 +419,24 @@ class Future:
         """
         Create a new Future instance.
 
+        支持T2412.CFE/T2412.CFFEX等市场后缀、T.CFE/T00等连续代码及小写代码,
+        市场后缀统一为CFE, 无法解析时抛出ValueError
+
         Args:
             code: Future contract code
         """
 
     def copy(self) -> Future: ...
+    def is_continuous(self) -> bool:
+        """是否为连续合约代码"""
+
+    def resolve(self, date: date | str) -> Future:
+        """
+        将当季(T00)、下季(T01)、隔季(T02)连续代码解析为date当日对应的具体合约, 具体合约返回自身
+
+        主力连续(T.CFE)需要根据持仓量判断, 参见pybond.pl.build_continuous
+        """
+
     def is_deliverable(
         self,
         carry_date: date,
@@ -154,6 +452,81 @@ class Future:
             delivery_date: 可以传入已计算过的期货配对缴款日避免重复计算
         """
 
+    def contract_spec(self) -> dict[str, float | int | None]:
+        """
+        获取期货合约适用的合约规格, 按合约最后交易日选取
+
+        包含合约面值(contract_size)、合约乘数(multiplier)、最小变动价位(tick_size)、
+        涨跌停板比例(price_limit)、最低保证金比例(margin_ratio)、虚拟券票面利率(fictitious_cp_rate)
+        及可交割券的发行期限上限和剩余期限范围
+        """
+
+    def delivery_schedule(
+        self, intention_date: date | str | None = None
+    ) -> dict[str, date | bool]:
+        """
+        获取期货合约的交割流程
+
+        滚动交割: 交割月首个交易日至最后交易日前一交易日申报交割意向, 申报当日配对,
+        其后第一、二个交易日分别为缴款日和交券日;
+        集中交割: 最后交易日申报, 其后第一、二、三个交易日分别为配对日、缴款日和交券日
+
+        Args:
+            intention_date: 交割意向申报日, 为None或最后交易日时返回集中交割的流程
+
+        Returns:
+            包含intention_date、matching_date、payment_date、transfer_date和rolling的字典
+        """
+
+    def rolling_delivery_schedules(self) -> list[dict[str, date | bool]]:
+        """获取交割月内全部可申报滚动交割的交割流程, 按申报日排列"""
+
+    def multiplier(self) -> float:
+        """获取合约乘数, 即合约面值 / 100"""
+
+    @staticmethod
+    def register_products(path: str | None = None) -> list[str]:
+        """
+        从json配置文件登记期货品种或修订品种规则, 返回登记的品种代码前缀
+
+        文件中任一品种不合法时不登记任何品种
+
+        Args:
+            path: 配置文件路径, 内容为品种定义的数组, 每个品种包含prefix、name、listing_date、
+                first_contract及按生效日期排列的rules(字段同contract_spec);
+                为None时使用FUTURE_PRODUCTS_PATH(默认为~/tea-bond/future_products.json), 文件不存在时不登记
+        """
+
+    def deliverable_basket(self, mkt: str = "IB") -> list[Bond]:
+        """
+        扫描本地债券映射文件, 获取期货合约的可交割券篮子(按债券代码排序)
+
+        Args:
+            mkt: 只保留该市场的债券代码, 避免同一只国债在多个市场重复出现
+        """
+
+    @staticmethod
+    def roll_window(
+        dates: list[date],
+        near_oi: list[float],
+        far_oi: list[float],
+        start_ratio: float = 0.3,
+        end_ratio: float = 0.3,
+    ) -> tuple[date, date | None, date | None] | None:
+        """
+        根据近月和远月合约的持仓量序列识别移仓窗口, 缺失的持仓量(NaN)会被跳过
+
+        Args:
+            dates: 交易日期
+            near_oi: 近月合约持仓量
+            far_oi: 远月合约持仓量
+            start_ratio: 远月持仓量达到近月持仓量的该比例时开始移仓
+            end_ratio: 近月持仓量降至远月持仓量的该比例以下时移仓结束
+
+        Returns:
+            (开始日期, 远月持仓量超过近月的日期, 结束日期), 远月持仓量从未达到start_ratio时返回None
+        """
+
     def last_trading_date(self) -> date:
         """
         计算期货合约的最后交易日
@@ -222,6 +595,64 @@ class TfEvaluator:
         reinvest_rate: float | None = None,
     ) -> None: ...
     def copy(self) -> TfEvaluator: ...
+    @staticmethod
+    def ctd_analysis(
+        future: Future | str,
+        bonds: list[Bond | str | int],
+        bond_ytms: list[float],
+        date: date | str,
+        future_price: float,
+        capital_rate: float,
+        reinvest_rate: float | None = None,
+    ) -> tuple[list[TfEvaluator], float | None]:
+        """
+        对一篮子债券进行CTD分析
+
+        剔除非可交割券后按IRR从高到低排序, 返回排序后的计算结果及CTD与次便宜券的IRR之差
+        """
+    @staticmethod
+    def ctd_scenarios(
+        future: Future | str,
+        bonds: list[Bond | str | int],
+        bond_ytms: list[float],
+        date: date | str,
+        future_price: float,
+        capital_rate: float,
+        parallel_shifts: list[float],
+        twist_shifts: list[float] = [0.0],
+        pivot: float = 7.0,
+        reinvest_rate: float | None = None,
+    ) -> list[tuple[float, float, float, TfEvaluator]]:
+        """
+        CTD切换情景分析
+
+        剩余期限为t年的债券收益率变动 = 平移幅度 + 扭转幅度 * (t - pivot), 对平移与扭转幅度的全部组合
+        重新计算期货理论价格和CTD, 返回每个情景的(平移幅度, 扭转幅度, 期货理论价格, CTD计算结果)
+        """
+    @staticmethod
+    def delivery_option(
+        future: Future | str,
+        bonds: list[Bond | str | int],
+        bond_ytms: list[float],
+        date: date | str,
+        future_price: float,
+        capital_rate: float,
+        vol: float,
+        model: Literal["normal", "lognormal"] = "normal",
+        reinvest_rate: float | None = None,
+    ) -> tuple[float, float, float, list[tuple[str, float, float]]]:
+        """
+        交割期权(品质期权)估值
+
+        假设各可交割券收益率在交割日同向变动, 对收益率分布数值积分得到交割日各券折算价格最小值的期望
+
+        Args:
+            vol: 年化收益率波动率, 正态模型为绝对波动(如0.005), 对数正态模型为相对波动(如0.2)
+
+        Returns:
+            (交割期权价值, 期货理论价格, 期权调整期货DV01, [(债券代码, 期权调整净基差, 成为CTD的概率)]),
+            债券按IRR从高到低排列
+        """
     def date(self) -> date:
         """获取计算日期"""
 
@@ -320,6 +751,26 @@ class TfEvaluator:
     def with_irr(self) -> TfEvaluator:
         """计算内部收益率IRR"""
 
+    def implied_repo(self, future_price: float) -> float:
+        """计算给定期货价格对应的隐含回购利率, 与IRR的计算口径一致"""
+
+    def theoretical_price(self, repo_rate: float) -> float:
+        """
+        计算期货理论价格, 即IRR等于回购利率时的期货价格
+
+        期货理论价格 = (远期全价 - 交割应计利息) / 转换因子, 未定义再投资利率时期间付息按回购利率再投资
+        """
+
+    def early_delivery(self, intention_date: date | str) -> TfEvaluator:
+        """
+        按交割意向申报日为intention_date的滚动交割重新计算
+
+        缴款日替换为滚动交割的缴款日, 发票价格、持有收益和IRR等均按该日计算, 转换因子保持不变
+        """
+
+    def invoice_amount(self, contracts: float = 1.0) -> float:
+        """计算交割货款, 即期货全价(发票价格) * 合约乘数 * 交割手数"""
+
     def with_future_ytm(self, use_deliver_date: bool = True) -> TfEvaluator:
         """计算期货隐含收益率"""
 
@@ -458,3 +909,79 @@ class TfEvaluator:
             ctd_bond: CTD债券代码
             ctd_ytm: CTD债券收益率
         """
+
+    def hedge_contracts(
+        self,
+        face_value: float,
+        ctd_bond: Bond | str | int | None = None,
+        ctd_ytm: float = float("nan"),
+        beta: float | None = None,
+    ) -> tuple[float, float, float | None]:
+        """
+        计算债券头寸的期货套保手数, 正数表示卖出期货
+
+        合约面值2年期为200万元, 其余为100万元
+
+        Args:
+            face_value: 债券头寸面值(元)
+            ctd_bond: 计算期货DV01使用的CTD债券, 如果为None则使用当前债券
+            ctd_ytm: CTD债券收益率
+            beta: 债券价格变动对期货价格变动的回归系数, 为None时不计算回归beta手数
+
+        Returns:
+            (DV01中性手数, 转换因子比率手数, 回归beta手数)
+        """
+
+    @staticmethod
+    def regression_beta(bond_prices: list[float], future_prices: list[float]) -> float:
+        """由债券价格和期货价格序列估计回归beta, 即债券价格变动对期货价格变动的OLS斜率"""
+
+    @staticmethod
+    def calendar_spread(
+        near: TfEvaluator, far: TfEvaluator, repo_rate: float
+    ) -> tuple[float, float, float]:
+        """
+        跨期价差分析, 近月和远月合约分别以各自的CTD计算
+
+        Args:
+            near: 近月合约及其CTD的期现分析
+            far: 远月合约及其CTD的期现分析
+            repo_rate: 计算理论价差使用的回购利率
+
+        Returns:
+            (市场价差, 理论价差, 隐含远期回购利率), 价差为近月价格 - 远月价格
+        """
+
+def build_continuous_future(
+    dates: list[date],
+    codes: list[str],
+    prices: list[float],
+    open_interests: list[float],
+    volumes: list[float],
+    rule: Literal["oi", "open_interest", "volume", "days"] = "oi",
+    adjust: Literal["ratio", "diff", "difference", "none"] = "ratio",
+    roll_days: int = 5,
+) -> tuple[
+    list[date],
+    list[str],
+    list[float],
+    list[float],
+    list[tuple[date, date, str, str, float, float]],
+]:
+    """
+    由各合约的行情构建主力连续合约, 参见pybond.pl.build_continuous
+
+    Args:
+        dates: 行情日期
+        codes: 行情对应的合约代码
+        prices: 合约价格
+        open_interests: 合约持仓量, 缺失时为NaN
+        volumes: 合约成交量, 缺失时为NaN
+        rule: 换月规则, "oi"按持仓量、"volume"按成交量、"days"在最后交易日前roll_days个交易日换月
+        adjust: 复权方式, "ratio"按比例、"diff"按价差、"none"不复权
+        roll_days: rule="days"时最后交易日前的交易日数
+
+    Returns:
+        (日期, 主力合约, 原始价格, 复权价格, 换月记录),
+        换月记录为(换月日期, 换月信号日期, 旧合约, 新合约, 旧合约价格, 新合约价格)
+    """
@@ -1,9 +1,11 @@
+use std::cell::RefCell;
+
 use anyhow::Result;
 use pyo3_polars::derive::polars_expr;
 use pyo3_polars::export::polars_core::utils::CustomIterTools;
 use serde::Deserialize;
 use tea_bond::export::calendar::Calendar;
-use tea_bond::{BondYtm, CachedBond, Future, Market, TfEvaluator};
+use tea_bond::{BondYtm, CachedBond, Future, Market, TfEvaluator, YieldCurve};
 use tevec::export::arrow as polars_arrow;
 use tevec::export::polars::prelude::*;
 
@@ -568,6 +570,143 @@ fn bonds_maturity_date(inputs: &[Series], kwargs: EvaluatorBatchParams) -> Polar
     Ok(result.into_series())
 }
 
+#[polars_expr(output_type=Float64)]
+fn bonds_convexity(inputs: &[Series], kwargs: EvaluatorBatchParams) -> PolarsResult<Series> {
+    let result: Float64Chunked = batch_eval(
+        inputs,
+        kwargs,
+        Ok,
+        |e: &TfEvaluator| {
+            e.bond
+                .calc_convexity(e.bond.ytm(), e.date, None, None)
+                .ok()
+                .filter(|v| !v.is_nan())
+        },
+        false,
+        true,
+    )?
+    .into_iter()
+    .collect_trusted();
+    Ok(result.into_series())
+}
+
+#[polars_expr(output_type=Float64)]
+fn bonds_dollar_duration(inputs: &[Series], kwargs: EvaluatorBatchParams) -> PolarsResult<Series> {
+    let result: Float64Chunked = batch_eval(
+        inputs,
+        kwargs,
+        Ok,
+        |e: &TfEvaluator| {
+            e.bond
+                .calc_dollar_duration(e.bond.ytm(), e.date, None, None)
+                .ok()
+                .filter(|v| !v.is_nan())
+        },
+        false,
+        true,
+    )?
+    .into_iter()
+    .collect_trusted();
+    Ok(result.into_series())
+}
+
+#[polars_expr(output_type=Float64)]
+fn bonds_dv01(inputs: &[Series], kwargs: EvaluatorBatchParams) -> PolarsResult<Series> {
+    let result: Float64Chunked = batch_eval(
+        inputs,
+        kwargs,
+        Ok,
+        |e: &TfEvaluator| {
+            e.bond
+                .calc_dv01(e.bond.ytm(), e.date, None, None)
+                .ok()
+                .filter(|v| !v.is_nan())
+        },
+        false,
+        true,
+    )?
+    .into_iter()
+    .collect_trusted();
+    Ok(result.into_series())
+}
+
+#[derive(Deserialize)]
+struct CurveKwargs {
+    tenors: Vec<f64>,
+    zero_rates: Vec<f64>,
+    #[serde(default)]
+    key_tenors: Vec<f64>,
+    #[serde(default)]
+    key_index: usize,
+}
+
+impl CurveKwargs {
+    /// 校验参数并构造曲线, 基准日在计算时设为计算日
+    fn curve(&self) -> PolarsResult<YieldCurve> {
+        YieldCurve::new(
+            Default::default(),
+            self.tenors.clone(),
+            self.zero_rates.clone(),
+        )
+        .map_err(|e| PolarsError::ComputeError(format!("Invalid yield curve: {e}").into()))
+    }
+}
+
+/// 以计算日为基准日使用曲线, 逐行计算基于曲线的风险指标及利差
+///
+/// 曲线只构造一次, 计算日变化时仅更新基准日
+fn bonds_curve_eval<F>(inputs: &[Series], curve: YieldCurve, func: F) -> PolarsResult<Series>
+where
+    F: Fn(&BondYtm, &YieldCurve, chrono::NaiveDate) -> Result<f64>,
+{
+    let params = EvaluatorBatchParams {
+        reinvest_rate: None,
+        use_deliver_date: None,
+    };
+    let curve = RefCell::new(curve);
+    let result: Float64Chunked = batch_eval(
+        inputs,
+        params,
+        Ok,
+        |e: &TfEvaluator| {
+            let mut curve = curve.borrow_mut();
+            if curve.ref_date != e.date {
+                curve.ref_date = e.date;
+            }
+            func(&e.bond, &curve, e.date).ok().filter(|v| !v.is_nan())
+        },
+        false,
+        true,
+    )?
+    .into_iter()
+    .collect_trusted();
+    Ok(result.into_series())
+}
+
+#[polars_expr(output_type=Float64)]
+fn bonds_pv01(inputs: &[Series], kwargs: CurveKwargs) -> PolarsResult<Series> {
+    bonds_curve_eval(inputs, kwargs.curve()?, |bond, curve, date| {
+        bond.calc_pv01(curve, date)
+    })
+}
+
+#[polars_expr(output_type=Float64)]
+fn bonds_key_rate_duration(inputs: &[Series], kwargs: CurveKwargs) -> PolarsResult<Series> {
+    let curve = kwargs.curve()?;
+    let CurveKwargs {
+        key_tenors,
+        key_index,
+        ..
+    } = kwargs;
+    // 关键期限及序号有误时直接报错, 而不是逐行返回空值
+    curve
+        .key_rate_shift(&key_tenors, key_index, 0.)
+        .map_err(|e| PolarsError::ComputeError(format!("Invalid key tenors: {e}").into()))?;
+    bonds_curve_eval(inputs, curve, move |bond, curve, date| {
+        bond.calc_key_rate_duration(curve, date, &key_tenors, key_index)
+    })
+}
+
 #[polars_expr(output_type=Float64)]
 fn bonds_calc_ytm_with_price(inputs: &[Series]) -> PolarsResult<Series> {
     let dirty_price_se = auto_cast!(Float64(&inputs[2]));
@@ -628,6 +767,27 @@ fn bonds_calc_ytm_with_price(inputs: &[Series]) -> PolarsResult<Series> {
     Ok(result.into_series())
 }
 
+#[polars_expr(output_type=Float64)]
+fn bonds_g_spread(inputs: &[Series], kwargs: CurveKwargs) -> PolarsResult<Series> {
+    bonds_curve_eval(inputs, kwargs.curve()?, |bond, curve, date| {
+        Ok(bond.calc_g_spread(curve, date))
+    })
+}
+
+#[polars_expr(output_type=Float64)]
+fn bonds_i_spread(inputs: &[Series], kwargs: CurveKwargs) -> PolarsResult<Series> {
+    bonds_curve_eval(inputs, kwargs.curve()?, |bond, curve, date| {
+        Ok(bond.calc_i_spread(curve, date))
+    })
+}
+
+#[polars_expr(output_type=Float64)]
+fn bonds_z_spread(inputs: &[Series], kwargs: CurveKwargs) -> PolarsResult<Series> {
+    bonds_curve_eval(inputs, kwargs.curve()?, |bond, curve, date| {
+        bond.calc_z_spread(curve, date)
+    })
+}
+
 #[derive(Deserialize)]
 struct FindWorkdayKwargs {
     market: Market,
@@ -1,5 +1,6 @@
 use std::{ops::Deref, path::PathBuf};
 
+use crate::curve::PyYieldCurve;
 use crate::utils::{extract_date, extract_date2};
 use chrono::NaiveDate;
 use pyo3::{exceptions::PyValueError, prelude::*, types::PyType};
@@ -244,6 +245,105 @@ impl PyBond {
         self.set_coupon_rate(cp_rate);
     }
 
+    /// 票面利率调整计划, 累进利率债券适用
+    ///
+    /// 返回(生效日, 票面利率)列表
+    #[getter]
+    pub fn cp_rate_schedule(&self) -> Vec<(NaiveDate, f64)> {
+        self.0
+            .cp_rate_schedule
+            .iter()
+            .map(|step| (step.start_date, step.rate))
+            .collect()
+    }
+
+    #[setter]
+    pub fn set_cp_rate_schedule(&mut self, schedule: Vec<(Bound<'_, PyAny>, f64)>) -> PyResult<()> {
+        let schedule = schedule
+            .iter()
+            .map(|(date, rate)| Ok(CouponRateStep::new(extract_date(date)?, *rate)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let raw = self.0.as_mut_ptr();
+        unsafe {
+            let bond = &mut *raw;
+            bond.cp_rate_schedule = schedule;
+        }
+        Ok(())
+    }
+
+    /// 分期还本计划
+    ///
+    /// 返回(还本日, 偿还本金占发行面值的比例)列表
+    #[getter]
+    pub fn amortization(&self) -> Vec<(NaiveDate, f64)> {
+        self.0
+            .amortization
+            .iter()
+            .map(|r| (r.date, r.ratio))
+            .collect()
+    }
+
+    #[setter]
+    pub fn set_amortization(&mut self, schedule: Vec<(Bound<'_, PyAny>, f64)>) -> PyResult<()> {
+        let schedule = schedule
+            .iter()
+            .map(|(date, ratio)| Ok(PrincipalRepayment::new(extract_date(date)?, *ratio)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let raw = self.0.as_mut_ptr();
+        unsafe {
+            let bond = &mut *raw;
+            bond.amortization = schedule;
+        }
+        Ok(())
+    }
+
+    /// 行权条款
+    ///
+    /// 返回(行权类型, 行权日, 行权价格)列表, 行权类型为"Call"或"Put"
+    #[getter]
+    pub fn embedded_options(&self) -> Vec<(String, NaiveDate, f64)> {
+        self.0
+            .embedded_options
+            .iter()
+            .map(|o| {
+                (
+                    format!("{:?}", o.option_type),
+                    o.exercise_date,
+                    o.exercise_price,
+                )
+            })
+            .collect()
+    }
+
+    #[setter]
+    pub fn set_embedded_options(
+        &mut self,
+        options: Vec<(String, Bound<'_, PyAny>, f64)>,
+    ) -> PyResult<()> {
+        let options = options
+            .iter()
+            .map(|(option_type, date, price)| {
+                let option_type: OptionType = option_type.parse()?;
+                Ok(EmbeddedOption::new(
+                    option_type,
+                    extract_date(date)?,
+                    *price,
+                ))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let raw = self.0.as_mut_ptr();
+        unsafe {
+            let bond = &mut *raw;
+            bond.embedded_options = options;
+        }
+        Ok(())
+    }
+
+    /// 指定日期(含当日还本)之后的剩余本金
+    pub fn remaining_par(&self, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.remaining_par(extract_date(date)?))
+    }
+
     /// 基准利率, 浮动付息债券适用
     #[getter]
     pub fn base_rate(&self) -> Option<f64> {
@@ -401,6 +501,37 @@ impl PyBond {
             .remain_cp_dates_until(date, until_date, next_cp_date)?)
     }
 
+    /// 现金流计划
+    ///
+    /// 返回(名义付息日, 实际支付日, 计息起始日, 计息截止日, 利息, 本金)列表,
+    /// 传入date时仅返回该日之后的剩余现金流
+    #[pyo3(signature = (date=None))]
+    #[allow(clippy::type_complexity)]
+    pub fn cash_flows(
+        &self,
+        date: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<(NaiveDate, NaiveDate, NaiveDate, NaiveDate, f64, f64)>> {
+        let date = date.map(extract_date).transpose()?;
+        let schedule = self.0.cash_flow_schedule()?;
+        let flows = match date {
+            Some(date) => schedule.remain(date),
+            None => &schedule[..],
+        };
+        Ok(flows
+            .iter()
+            .map(|cf| {
+                (
+                    cf.nominal_date,
+                    cf.payment_date,
+                    cf.accrual_start,
+                    cf.accrual_end,
+                    cf.coupon,
+                    cf.principal,
+                )
+            })
+            .collect())
+    }
+
     /// 计算应计利息
     ///
     /// 银行间和交易所的计算规则不同,银行间是算头不算尾,而交易所是算头又算尾
@@ -466,6 +597,36 @@ impl PyBond {
             .calc_ytm_with_price(dirty_price, date, cp_dates, remain_cp_num)?)
     }
 
+    /// 通过债券全价计算到下一赎回日的收益率
+    pub fn calc_ytc(&self, dirty_price: f64, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_ytc(dirty_price, extract_date(date)?)?)
+    }
+
+    /// 通过债券全价计算到下一回售日的收益率
+    pub fn calc_ytp(&self, dirty_price: f64, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_ytp(dirty_price, extract_date(date)?)?)
+    }
+
+    /// 通过债券全价计算最差收益率
+    pub fn calc_ytw(&self, dirty_price: f64, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_ytw(dirty_price, extract_date(date)?)?)
+    }
+
+    /// 到下一赎回日的修正久期
+    pub fn calc_ytc_duration(&self, ytc: f64, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_ytc_duration(ytc, extract_date(date)?)?)
+    }
+
+    /// 到下一回售日的修正久期
+    pub fn calc_ytp_duration(&self, ytp: f64, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_ytp_duration(ytp, extract_date(date)?)?)
+    }
+
+    /// 最差收益率对应行权路径的修正久期
+    pub fn calc_ytw_duration(&self, dirty_price: f64, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_ytw_duration(dirty_price, extract_date(date)?)?)
+    }
+
     /// 计算麦考利久期
     #[pyo3(signature = (ytm, date, cp_dates=None, remain_cp_num=None))]
     pub fn calc_macaulay_duration(
@@ -497,4 +658,285 @@ impl PyBond {
         let remain_cp_num: Option<i32> = remain_cp_num.map(|d| d.extract()).transpose()?;
         Ok(self.0.calc_duration(ytm, date, cp_dates, remain_cp_num)?)
     }
+
+    /// 计算凸性
+    #[pyo3(signature = (ytm, date, cp_dates=None, remain_cp_num=None))]
+    pub fn calc_convexity(
+        &self,
+        ytm: f64,
+        date: &Bound<'_, PyAny>,
+        cp_dates: Option<&Bound<'_, PyAny>>,
+        remain_cp_num: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<f64> {
+        let date = extract_date(date)?;
+        let cp_dates: Option<(NaiveDate, NaiveDate)> = cp_dates.map(extract_date2).transpose()?;
+        let remain_cp_num: Option<i32> = remain_cp_num.map(|d| d.extract()).transpose()?;
+        Ok(self.0.calc_convexity(ytm, date, cp_dates, remain_cp_num)?)
+    }
+
+    /// 计算美元久期(修正久期 * 全价)
+    #[pyo3(signature = (ytm, date, cp_dates=None, remain_cp_num=None))]
+    pub fn calc_dollar_duration(
+        &self,
+        ytm: f64,
+        date: &Bound<'_, PyAny>,
+        cp_dates: Option<&Bound<'_, PyAny>>,
+        remain_cp_num: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<f64> {
+        let date = extract_date(date)?;
+        let cp_dates: Option<(NaiveDate, NaiveDate)> = cp_dates.map(extract_date2).transpose()?;
+        let remain_cp_num: Option<i32> = remain_cp_num.map(|d| d.extract()).transpose()?;
+        Ok(self
+            .0
+            .calc_dollar_duration(ytm, date, cp_dates, remain_cp_num)?)
+    }
+
+    /// 计算DV01, 收益率下降1bp时每百元面值全价的变动
+    #[pyo3(signature = (ytm, date, cp_dates=None, remain_cp_num=None))]
+    pub fn calc_dv01(
+        &self,
+        ytm: f64,
+        date: &Bound<'_, PyAny>,
+        cp_dates: Option<&Bound<'_, PyAny>>,
+        remain_cp_num: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<f64> {
+        let date = extract_date(date)?;
+        let cp_dates: Option<(NaiveDate, NaiveDate)> = cp_dates.map(extract_date2).transpose()?;
+        let remain_cp_num: Option<i32> = remain_cp_num.map(|d| d.extract()).transpose()?;
+        Ok(self.0.calc_dv01(ytm, date, cp_dates, remain_cp_num)?)
+    }
+
+    /// 按即期收益率曲线计算全价
+    pub fn calc_dirty_price_with_curve(
+        &self,
+        curve: &PyYieldCurve,
+        date: &Bound<'_, PyAny>,
+    ) -> PyResult<f64> {
+        Ok(self
+            .0
+            .calc_dirty_price_with_curve(&curve.0, extract_date(date)?)?)
+    }
+
+    /// 相对曲线的贵贱程度, 市场全价对应的到期收益率减去曲线定价对应的到期收益率, 为正表示偏便宜
+    pub fn calc_rich_cheap(
+        &self,
+        curve: &PyYieldCurve,
+        dirty_price: f64,
+        date: &Bound<'_, PyAny>,
+    ) -> PyResult<f64> {
+        Ok(self
+            .0
+            .calc_rich_cheap(&curve.0, dirty_price, extract_date(date)?)?)
+    }
+
+    /// 计算G利差, 到期收益率减去按剩余期限插值的国债收益率
+    pub fn calc_g_spread(
+        &self,
+        ytm: f64,
+        govt_curve: &PyYieldCurve,
+        date: &Bound<'_, PyAny>,
+    ) -> PyResult<f64> {
+        Ok(self
+            .0
+            .calc_g_spread(ytm, &govt_curve.0, extract_date(date)?))
+    }
+
+    /// 计算I利差, 到期收益率减去按剩余期限插值的利率互换利率
+    pub fn calc_i_spread(
+        &self,
+        ytm: f64,
+        swap_curve: &PyYieldCurve,
+        date: &Bound<'_, PyAny>,
+    ) -> PyResult<f64> {
+        Ok(self
+            .0
+            .calc_i_spread(ytm, &swap_curve.0, extract_date(date)?))
+    }
+
+    /// 计算Z利差, 即期利率曲线整体加上该利差后对剩余现金流折现等于全价
+    pub fn calc_z_spread(
+        &self,
+        dirty_price: f64,
+        curve: &PyYieldCurve,
+        date: &Bound<'_, PyAny>,
+    ) -> PyResult<f64> {
+        Ok(self
+            .0
+            .calc_z_spread(dirty_price, &curve.0, extract_date(date)?)?)
+    }
+
+    /// 通过全价计算远期全价, 期间现金流按再投资利率(默认为回购利率)再投资至远期结算日
+    #[pyo3(signature = (dirty_price, date, forward_date, repo_rate, reinvest_rate=None))]
+    pub fn calc_forward_dirty_price(
+        &self,
+        dirty_price: f64,
+        date: &Bound<'_, PyAny>,
+        forward_date: &Bound<'_, PyAny>,
+        repo_rate: f64,
+        reinvest_rate: Option<f64>,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_forward_dirty_price(
+            dirty_price,
+            extract_date(date)?,
+            extract_date(forward_date)?,
+            repo_rate,
+            reinvest_rate,
+        )?)
+    }
+
+    /// 通过全价计算远期净价
+    #[pyo3(signature = (dirty_price, date, forward_date, repo_rate, reinvest_rate=None))]
+    pub fn calc_forward_clean_price(
+        &self,
+        dirty_price: f64,
+        date: &Bound<'_, PyAny>,
+        forward_date: &Bound<'_, PyAny>,
+        repo_rate: f64,
+        reinvest_rate: Option<f64>,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_forward_clean_price(
+            dirty_price,
+            extract_date(date)?,
+            extract_date(forward_date)?,
+            repo_rate,
+            reinvest_rate,
+        )?)
+    }
+
+    /// 通过全价和远期全价计算隐含回购利率, 未指定再投资利率时按隐含回购利率再投资
+    #[pyo3(signature = (dirty_price, date, forward_date, forward_dirty_price, reinvest_rate=None))]
+    pub fn calc_implied_repo(
+        &self,
+        dirty_price: f64,
+        date: &Bound<'_, PyAny>,
+        forward_date: &Bound<'_, PyAny>,
+        forward_dirty_price: f64,
+        reinvest_rate: Option<f64>,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_implied_repo(
+            dirty_price,
+            extract_date(date)?,
+            extract_date(forward_date)?,
+            forward_dirty_price,
+            reinvest_rate,
+        )?)
+    }
+
+    /// 计算PV01, 即期收益率曲线整体下移1bp时每百元面值全价的变动
+    pub fn calc_pv01(&self, curve: &PyYieldCurve, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.calc_pv01(&curve.0, extract_date(date)?)?)
+    }
+
+    /// 计算关键期限久期, 返回与key_tenors一一对应的久期列表
+    pub fn calc_key_rate_durations(
+        &self,
+        curve: &PyYieldCurve,
+        date: &Bound<'_, PyAny>,
+        key_tenors: Vec<f64>,
+    ) -> PyResult<Vec<f64>> {
+        Ok(self
+            .0
+            .calc_key_rate_durations(&curve.0, extract_date(date)?, &key_tenors)?)
+    }
+
+    /// 浮动利率债券的现金流计划, 已重置的付息周期使用定盘利率, 之后的付息周期使用曲线推算的远期利率
+    ///
+    /// fixings为(定盘日, 基准利率)列表, 返回值格式同cash_flows
+    #[allow(clippy::type_complexity)]
+    pub fn floating_cash_flows(
+        &self,
+        date: &Bound<'_, PyAny>,
+        fixings: Vec<(Bound<'_, PyAny>, f64)>,
+        curve: &PyYieldCurve,
+    ) -> PyResult<Vec<(NaiveDate, NaiveDate, NaiveDate, NaiveDate, f64, f64)>> {
+        let schedule = self.0.floating_cash_flow_schedule(
+            extract_date(date)?,
+            &extract_fixings(fixings)?,
+            &curve.0,
+        )?;
+        Ok(schedule
+            .iter()
+            .map(|cf| {
+                (
+                    cf.nominal_date,
+                    cf.payment_date,
+                    cf.accrual_start,
+                    cf.accrual_end,
+                    cf.coupon,
+                    cf.principal,
+                )
+            })
+            .collect())
+    }
+
+    /// 浮动利率债券的应计利息, 按当期重置后的票面利息计提
+    pub fn calc_frn_accrued_interest(
+        &self,
+        date: &Bound<'_, PyAny>,
+        fixings: Vec<(Bound<'_, PyAny>, f64)>,
+        curve: &PyYieldCurve,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_frn_accrued_interest(
+            extract_date(date)?,
+            &extract_fixings(fixings)?,
+            &curve.0,
+        )?)
+    }
+
+    /// 通过贴现利差计算浮动利率债券全价
+    pub fn calc_frn_dirty_price(
+        &self,
+        date: &Bound<'_, PyAny>,
+        fixings: Vec<(Bound<'_, PyAny>, f64)>,
+        curve: &PyYieldCurve,
+        discount_margin: f64,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_frn_dirty_price(
+            extract_date(date)?,
+            &extract_fixings(fixings)?,
+            &curve.0,
+            discount_margin,
+        )?)
+    }
+
+    /// 通过浮动利率债券全价计算贴现利差
+    pub fn calc_discount_margin(
+        &self,
+        dirty_price: f64,
+        date: &Bound<'_, PyAny>,
+        fixings: Vec<(Bound<'_, PyAny>, f64)>,
+        curve: &PyYieldCurve,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_discount_margin(
+            dirty_price,
+            extract_date(date)?,
+            &extract_fixings(fixings)?,
+            &curve.0,
+        )?)
+    }
+
+    /// 利差久期, 贴现利差变动1bp时全价的相对变动
+    pub fn calc_spread_duration(
+        &self,
+        date: &Bound<'_, PyAny>,
+        fixings: Vec<(Bound<'_, PyAny>, f64)>,
+        curve: &PyYieldCurve,
+        discount_margin: f64,
+    ) -> PyResult<f64> {
+        Ok(self.0.calc_spread_duration(
+            extract_date(date)?,
+            &extract_fixings(fixings)?,
+            &curve.0,
+            discount_margin,
+        )?)
+    }
+}
+
+/// 由(定盘日, 基准利率)列表构建定盘利率序列
+fn extract_fixings(fixings: Vec<(Bound<'_, PyAny>, f64)>) -> PyResult<RateFixings> {
+    fixings
+        .iter()
+        .map(|(date, rate)| Ok((extract_date(date)?, *rate)))
+        .collect::<PyResult<Vec<_>>>()
+        .map(RateFixings::new)
 }
@@ -0,0 +1,201 @@
+use crate::utils::{extract_date, get_bond};
+use pyo3::prelude::*;
+use tea_bond::{BondYtm, Interpolation, NssCurve, NssFitOptions, RateCurve, YieldCurve};
+
+#[pyclass(name = "YieldCurve")]
+#[derive(Clone)]
+pub struct PyYieldCurve(pub YieldCurve);
+
+#[pymethods]
+impl PyYieldCurve {
+    #[new]
+    #[pyo3(signature = (ref_date, tenors, zero_rates, interpolation="linear"))]
+    pub fn new(
+        ref_date: &Bound<'_, PyAny>,
+        tenors: Vec<f64>,
+        zero_rates: Vec<f64>,
+        interpolation: &str,
+    ) -> PyResult<Self> {
+        let curve = YieldCurve::new(extract_date(ref_date)?, tenors, zero_rates)?;
+        Ok(Self(curve.with_interpolation(interpolation.parse()?)))
+    }
+
+    /// 由债券及其全价拔靴构造曲线
+    ///
+    /// bonds可以是Bond对象或债券代码
+    #[staticmethod]
+    #[pyo3(signature = (ref_date, bonds, dirty_prices, interpolation="linear"))]
+    pub fn bootstrap(
+        ref_date: &Bound<'_, PyAny>,
+        bonds: Vec<Bound<'_, PyAny>>,
+        dirty_prices: Vec<f64>,
+        interpolation: &str,
+    ) -> PyResult<Self> {
+        if bonds.len() != dirty_prices.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Length of bonds and dirty_prices mismatch",
+            ));
+        }
+        let instruments = bonds
+            .iter()
+            .zip(dirty_prices)
+            .map(|(bond, price)| Ok((tea_bond::Bond::clone(&get_bond(bond)?), price)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(Self(YieldCurve::bootstrap(
+            extract_date(ref_date)?,
+            &instruments,
+            interpolation.parse()?,
+        )?))
+    }
+
+    /// 由关键期限的平价收益率拔靴构造曲线
+    #[staticmethod]
+    #[pyo3(signature = (ref_date, tenors, par_yields, inst_freq=1, interpolation="linear"))]
+    pub fn from_par_yields(
+        ref_date: &Bound<'_, PyAny>,
+        tenors: Vec<f64>,
+        par_yields: Vec<f64>,
+        inst_freq: i32,
+        interpolation: &str,
+    ) -> PyResult<Self> {
+        Ok(Self(YieldCurve::bootstrap_par_yields(
+            extract_date(ref_date)?,
+            &tenors,
+            &par_yields,
+            inst_freq,
+            interpolation.parse()?,
+        )?))
+    }
+
+    /// 水平曲线
+    #[staticmethod]
+    pub fn flat(ref_date: &Bound<'_, PyAny>, rate: f64) -> PyResult<Self> {
+        Ok(Self(YieldCurve::flat(extract_date(ref_date)?, rate)))
+    }
+
+    /// 曲线基准日
+    #[getter]
+    pub fn ref_date(&self) -> chrono::NaiveDate {
+        self.0.ref_date
+    }
+
+    /// 关键期限(年)
+    #[getter]
+    pub fn tenors(&self) -> Vec<f64> {
+        self.0.tenors().to_vec()
+    }
+
+    /// 关键期限对应的即期利率
+    #[getter]
+    pub fn zero_rates(&self) -> Vec<f64> {
+        self.0.zero_rates().to_vec()
+    }
+
+    /// 插值方法
+    #[getter]
+    pub fn interpolation(&self) -> String {
+        format!("{:?}", self.0.interpolation())
+    }
+
+    /// 曲线平移, shift为平移的利率(如0.0001为1bp)
+    pub fn shift(&self, shift: f64) -> Self {
+        Self(self.0.shift(shift))
+    }
+
+    /// 期限为t年的即期利率
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        self.0.zero_rate_t(t)
+    }
+
+    /// 指定日期的贴现因子
+    pub fn discount_factor(&self, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.discount_factor(extract_date(date)?))
+    }
+
+    /// 两个日期之间的远期利率(单利)
+    pub fn forward_rate(&self, start: &Bound<'_, PyAny>, end: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self
+            .0
+            .forward_rate(extract_date(start)?, extract_date(end)?))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+#[pyclass(name = "NssCurve")]
+#[derive(Clone)]
+pub struct PyNssCurve(pub NssCurve);
+
+#[pymethods]
+impl PyNssCurve {
+    /// 用债券收益率拟合Nelson-Siegel(-Svensson)曲线
+    ///
+    /// 返回(拟合曲线, 全价残差, 收益率残差), 残差为市场值减模型值, 与输入债券顺序一致
+    #[staticmethod]
+    #[pyo3(signature = (date, bonds, ytms, model="nelson_siegel", objective="yield", duration_weighted=false))]
+    pub fn fit(
+        date: &Bound<'_, PyAny>,
+        bonds: Vec<Bound<'_, PyAny>>,
+        ytms: Vec<f64>,
+        model: &str,
+        objective: &str,
+        duration_weighted: bool,
+    ) -> PyResult<(Self, Vec<f64>, Vec<f64>)> {
+        if bonds.len() != ytms.len() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Length of bonds and ytms mismatch",
+            ));
+        }
+        let bonds = bonds
+            .iter()
+            .zip(ytms)
+            .map(|(bond, ytm)| Ok(BondYtm::new(get_bond(bond)?.0, ytm)))
+            .collect::<PyResult<Vec<_>>>()?;
+        let options = NssFitOptions {
+            model: model.parse()?,
+            objective: objective.parse()?,
+            duration_weighted,
+            ..Default::default()
+        };
+        let result = NssCurve::fit(&bonds, extract_date(date)?, &options)?;
+        Ok((
+            Self(result.curve),
+            result.price_residuals,
+            result.ytm_residuals,
+        ))
+    }
+
+    /// 曲线基准日
+    #[getter]
+    pub fn ref_date(&self) -> chrono::NaiveDate {
+        self.0.ref_date
+    }
+
+    /// 曲线模型
+    #[getter]
+    pub fn model(&self) -> String {
+        format!("{:?}", self.0.model)
+    }
+
+    /// 曲线参数, Nelson-Siegel为[β0, β1, β2, τ1], Svensson为[β0, β1, β2, β3, τ1, τ2]
+    #[getter]
+    pub fn params(&self) -> Vec<f64> {
+        self.0.params()
+    }
+
+    /// 期限为t年的即期利率
+    pub fn zero_rate(&self, t: f64) -> f64 {
+        self.0.rate(t)
+    }
+
+    /// 指定日期的贴现因子
+    pub fn discount_factor(&self, date: &Bound<'_, PyAny>) -> PyResult<f64> {
+        Ok(self.0.discount_factor(extract_date(date)?))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
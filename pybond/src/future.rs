@@ -1,11 +1,29 @@
 use std::{ops::Deref, sync::Arc};
 
+use crate::bond::PyBond;
 use crate::utils::extract_date;
 use chrono::NaiveDate;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 use std::str::FromStr;
-use tea_bond::{Future, FutureType};
+use tea_bond::{
+    AdjustMode, CachedBond, ContinuousBuilder, ContractBar, DeliverySchedule, Future, FutureType,
+    RollRule, RollWindow,
+};
+
+fn delivery_schedule_dict<'py>(
+    py: Python<'py>,
+    schedule: &DeliverySchedule,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("intention_date", schedule.intention_date)?;
+    dict.set_item("matching_date", schedule.matching_date)?;
+    dict.set_item("payment_date", schedule.payment_date)?;
+    dict.set_item("transfer_date", schedule.transfer_date)?;
+    dict.set_item("rolling", schedule.rolling)?;
+    Ok(dict)
+}
 
 #[pyclass(name = "Future")]
 #[derive(Clone)]
@@ -29,8 +47,20 @@ impl Deref for PyFuture {
 #[pymethods]
 impl PyFuture {
     #[new]
-    pub fn new(code: &str) -> Self {
-        Self(Arc::new(Future::new(code)))
+    /// 解析期货代码, 支持T2412.CFE/T2412.CFFEX等市场后缀、T.CFE/T00等连续代码及小写代码
+    pub fn new(code: &str) -> PyResult<Self> {
+        let future = Future::parse(code).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self(Arc::new(future)))
+    }
+
+    /// 是否为连续合约代码
+    fn is_continuous(&self) -> bool {
+        self.0.is_continuous()
+    }
+
+    /// 将当季(T00)、下季(T01)、隔季(T02)连续代码解析为date当日对应的具体合约, 具体合约返回自身
+    fn resolve(&self, date: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self(Arc::new(self.0.resolve(extract_date(date)?)?)))
     }
 
     #[staticmethod]
@@ -93,6 +123,106 @@ impl PyFuture {
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 
+    /// 获取期货合约适用的合约规格, 按合约最后交易日选取
+    fn contract_spec<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let spec = self.0.contract_spec()?;
+        let dict = PyDict::new(py);
+        dict.set_item("contract_size", spec.contract_size)?;
+        dict.set_item("multiplier", spec.multiplier())?;
+        dict.set_item("tick_size", spec.tick_size)?;
+        dict.set_item("price_limit", spec.price_limit)?;
+        dict.set_item("margin_ratio", spec.margin_ratio)?;
+        dict.set_item("fictitious_cp_rate", spec.fictitious_cp_rate)?;
+        dict.set_item("max_issue_year", spec.max_issue_year)?;
+        dict.set_item("min_remain_year", spec.min_remain_year)?;
+        dict.set_item("max_remain_year", spec.max_remain_year)?;
+        Ok(dict)
+    }
+
+    #[pyo3(signature = (intention_date=None))]
+    /// 获取期货合约的交割流程
+    ///
+    /// 交割意向申报日为intention_date时按滚动交割推算, 为None或最后交易日时返回集中交割的流程;
+    /// 返回的字典包含intention_date、matching_date、payment_date、transfer_date和rolling
+    fn delivery_schedule<'py>(
+        &self,
+        py: Python<'py>,
+        intention_date: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let intention_date = intention_date.map(extract_date).transpose()?;
+        delivery_schedule_dict(py, &self.0.delivery_schedule(intention_date)?)
+    }
+
+    /// 获取交割月内全部可申报滚动交割的交割流程, 按申报日排列
+    fn rolling_delivery_schedules<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.0
+            .rolling_delivery_schedules()?
+            .iter()
+            .map(|schedule| delivery_schedule_dict(py, schedule))
+            .collect()
+    }
+
+    /// 获取合约乘数, 即合约面值 / 100
+    fn multiplier(&self) -> PyResult<f64> {
+        Ok(self.0.contract_spec()?.multiplier())
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (path=None))]
+    /// 从json配置文件登记期货品种或修订品种规则, 返回登记的品种代码前缀
+    ///
+    /// path为None时使用FUTURE_PRODUCTS_PATH(默认为~/tea-bond/future_products.json), 文件不存在时不登记;
+    /// 文件中任一品种不合法时不登记任何品种
+    fn register_products(path: Option<&str>) -> PyResult<Vec<String>> {
+        let types = match path {
+            Some(path) => FutureType::register_from_file(path)?,
+            None => FutureType::register_from_config()?,
+        };
+        Ok(types
+            .into_iter()
+            .map(|typ| typ.prefix().to_string())
+            .collect())
+    }
+
+    /// 扫描本地债券映射文件, 获取期货合约的可交割券篮子(按债券代码排序)
+    ///
+    /// mkt: 只保留该市场的债券代码, 避免同一只国债在多个市场重复出现
+    #[pyo3(signature = (mkt="IB"))]
+    fn deliverable_basket(&self, mkt: &str) -> PyResult<Vec<PyBond>> {
+        let bonds = self.0.deliverable_basket(mkt.parse()?)?;
+        Ok(bonds
+            .into_iter()
+            .map(|bond| PyBond(CachedBond::from_bond(bond)))
+            .collect())
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (dates, near_oi, far_oi, start_ratio=0.3, end_ratio=0.3))]
+    /// 根据近月和远月合约的持仓量序列识别移仓窗口, 缺失的持仓量(NaN)会被跳过
+    ///
+    /// 返回(开始日期, 远月持仓量超过近月的日期, 结束日期), 远月持仓量从未达到start_ratio时返回None
+    ///
+    /// Args:
+    ///     dates: 交易日期
+    ///     near_oi: 近月合约持仓量
+    ///     far_oi: 远月合约持仓量
+    ///     start_ratio: 远月持仓量达到近月持仓量的该比例时开始移仓
+    ///     end_ratio: 近月持仓量降至远月持仓量的该比例以下时移仓结束
+    fn roll_window(
+        dates: Vec<NaiveDate>,
+        near_oi: Vec<f64>,
+        far_oi: Vec<f64>,
+        start_ratio: f64,
+        end_ratio: f64,
+    ) -> PyResult<Option<(NaiveDate, Option<NaiveDate>, Option<NaiveDate>)>> {
+        let window =
+            RollWindow::from_open_interest(&dates, &near_oi, &far_oi, start_ratio, end_ratio)?;
+        Ok(window.map(|w| (w.start, w.crossover, w.end)))
+    }
+
     /// 计算期货合约的最后交易日
     ///
     /// 计算国债期货的最后交易日=合约到期月份的第二个星期五
@@ -164,3 +294,78 @@ impl PyFuture {
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
 }
+
+/// 由各合约的行情构建主力连续合约, 返回(日期, 主力合约, 原始价格, 复权价格, 换月记录)
+///
+/// 换月记录为(换月日期, 换月信号日期, 旧合约, 新合约, 旧合约价格, 新合约价格)
+#[pyfunction]
+#[pyo3(signature = (dates, codes, prices, open_interests, volumes, rule="oi", adjust="ratio", roll_days=5))]
+pub fn build_continuous_future(
+    dates: Vec<NaiveDate>,
+    codes: Vec<String>,
+    prices: Vec<f64>,
+    open_interests: Vec<f64>,
+    volumes: Vec<f64>,
+    rule: &str,
+    adjust: &str,
+    roll_days: i32,
+) -> PyResult<(
+    Vec<NaiveDate>,
+    Vec<String>,
+    Vec<f64>,
+    Vec<f64>,
+    Vec<(NaiveDate, NaiveDate, String, String, f64, f64)>,
+)> {
+    let n = dates.len();
+    if codes.len() != n || prices.len() != n || open_interests.len() != n || volumes.len() != n {
+        return Err(PyValueError::new_err(
+            "Length of dates, codes, prices, open_interests and volumes mismatch",
+        ));
+    }
+    let rule = match rule {
+        "oi" | "open_interest" => RollRule::OpenInterest,
+        "volume" => RollRule::Volume,
+        "days" => RollRule::DaysBeforeExpiry(roll_days),
+        _ => return Err(PyValueError::new_err(format!("Unknown roll rule: {rule}"))),
+    };
+    let mut contracts: Vec<(Future, Vec<ContractBar>)> = Vec::new();
+    for (i, code) in codes.iter().enumerate() {
+        let bar = ContractBar {
+            date: dates[i],
+            price: prices[i],
+            open_interest: open_interests[i],
+            volume: volumes[i],
+        };
+        let future = Future::new(code);
+        match contracts.iter_mut().find(|(f, _)| *f == future) {
+            Some((_, bars)) => bars.push(bar),
+            None => contracts.push((future, vec![bar])),
+        }
+    }
+    let series = ContinuousBuilder::new(rule, adjust.parse::<AdjustMode>()?).build(contracts)?;
+    let rolls = series
+        .rolls
+        .into_iter()
+        .map(|r| {
+            (
+                r.date,
+                r.signal_date,
+                r.from.code.to_string(),
+                r.to.code.to_string(),
+                r.from_price,
+                r.to_price,
+            )
+        })
+        .collect();
+    Ok((
+        series.dates,
+        series
+            .contracts
+            .into_iter()
+            .map(|f| f.code.to_string())
+            .collect(),
+        series.raw_prices,
+        series.prices,
+        rolls,
+    ))
+}
@@ -6,6 +6,7 @@ use pyo3::prelude::*;
 mod batch_eval;
 mod bond;
 mod calendar;
+mod curve;
 mod future;
 #[cfg(feature = "persist")]
 mod persist;
@@ -17,6 +18,7 @@ mod utils;
 use bond::PyBond;
 #[allow(unused_imports)]
 use bond_ffi::*;
+use curve::{PyNssCurve, PyYieldCurve};
 use future::PyFuture;
 use tf_evaluator::PyTfEvaluator;
 
@@ -32,6 +34,7 @@ fn pybond(m: &Bound<'_, PyModule>) -> PyResult<()> {
     #[cfg(feature = "download")]
     m.add_function(wrap_pyfunction!(bond::download_bond, m)?)?;
     m.add_function(wrap_pyfunction!(get_version, m)?)?;
+    m.add_function(wrap_pyfunction!(future::build_continuous_future, m)?)?;
     #[cfg(feature = "persist")]
     m.add_function(wrap_pyfunction!(persist::update_info_from_wind_sql_df, m)?)?;
     m.add_class::<calendar::Ib>()?;
@@ -39,5 +42,7 @@ fn pybond(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyBond>()?;
     m.add_class::<PyFuture>()?;
     m.add_class::<PyTfEvaluator>()?;
+    m.add_class::<PyYieldCurve>()?;
+    m.add_class::<PyNssCurve>()?;
     Ok(())
 }
@@ -77,6 +77,19 @@ pub fn update_info_from_wind_sql_df(df: PyDataFrame) -> PyResult<()> {
     let b_maturitydate = auto_cast!(String(col("b_info_maturitydate")));
     let b_referyield = auto_cast!(Float64(col("b_tendrst_referyield")));
     let b_issueprice = auto_cast!(Float64(col("b_info_issueprice")));
+    // 分期还本计划为可选列
+    let b_amortization = df
+        .column("b_info_amortization")
+        .or_else(|_| df.column("B_INFO_AMORTIZATION"))
+        .ok()
+        .map(|c| c.cast(&DataType::String))
+        .transpose()
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let b_amortization = b_amortization
+        .as_ref()
+        .map(|c| c.str())
+        .transpose()
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
     let iter = izip!(
         s_windcode.str().unwrap(),
         s_name.str().unwrap(),
@@ -141,6 +154,7 @@ pub fn update_info_from_wind_sql_df(df: PyDataFrame) -> PyResult<()> {
             b_info_maturitydate: maturity_date,
             b_tendrst_referyield: refer_yield,
             b_info_issueprice: issue_price,
+            b_info_amortization: b_amortization.and_then(|c| c.get(idx)).map(|s| s.into()),
         };
 
         let bond: Bond = row
@@ -4,6 +4,19 @@ use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use tea_bond::*;
 
+fn extract_basket(bonds: Vec<Bound<'_, PyAny>>, bond_ytms: Vec<f64>) -> PyResult<Vec<BondYtm>> {
+    if bonds.len() != bond_ytms.len() {
+        return Err(PyValueError::new_err(
+            "Length of bonds and bond_ytms mismatch",
+        ));
+    }
+    bonds
+        .iter()
+        .zip(bond_ytms)
+        .map(|(bond, ytm)| Ok(BondYtm::new(get_bond(bond)?.0, ytm)))
+        .collect()
+}
+
 #[pyclass(name = "TfEvaluator", subclass)]
 #[derive(Clone)]
 pub struct PyTfEvaluator(pub TfEvaluator);
@@ -50,6 +63,120 @@ impl PyTfEvaluator {
         }))
     }
 
+    /// 对一篮子债券进行CTD分析
+    ///
+    /// 剔除非可交割券后按IRR从高到低排序, 返回排序后的计算结果及CTD与次便宜券的IRR之差
+    #[staticmethod]
+    #[pyo3(signature = (future, bonds, bond_ytms, date, future_price, capital_rate, reinvest_rate=None))]
+    pub fn ctd_analysis(
+        future: &Bound<'_, PyAny>,
+        bonds: Vec<Bound<'_, PyAny>>,
+        bond_ytms: Vec<f64>,
+        date: &Bound<'_, PyAny>,
+        future_price: f64,
+        capital_rate: f64,
+        reinvest_rate: Option<f64>,
+    ) -> PyResult<(Vec<Self>, Option<f64>)> {
+        let future = FuturePrice {
+            future: get_future(future)?.0,
+            price: future_price,
+        };
+        let bonds = extract_basket(bonds, bond_ytms)?;
+        let analysis = CtdAnalysis::new(
+            extract_date(date)?,
+            future,
+            bonds,
+            capital_rate,
+            reinvest_rate,
+        )?;
+        let irr_gap = analysis.irr_gap();
+        Ok((analysis.evaluators.into_iter().map(Self).collect(), irr_gap))
+    }
+
+    /// CTD切换情景分析
+    ///
+    /// 对平移与扭转幅度的全部组合, 按剩余期限变动各可交割券收益率后重新计算期货理论价格和CTD,
+    /// 返回每个情景的(平移幅度, 扭转幅度, 期货理论价格, CTD计算结果)
+    #[staticmethod]
+    #[pyo3(signature = (future, bonds, bond_ytms, date, future_price, capital_rate, parallel_shifts, twist_shifts=vec![0.], pivot=7., reinvest_rate=None))]
+    pub fn ctd_scenarios(
+        future: &Bound<'_, PyAny>,
+        bonds: Vec<Bound<'_, PyAny>>,
+        bond_ytms: Vec<f64>,
+        date: &Bound<'_, PyAny>,
+        future_price: f64,
+        capital_rate: f64,
+        parallel_shifts: Vec<f64>,
+        twist_shifts: Vec<f64>,
+        pivot: f64,
+        reinvest_rate: Option<f64>,
+    ) -> PyResult<Vec<(f64, f64, f64, Self)>> {
+        let future = FuturePrice {
+            future: get_future(future)?.0,
+            price: future_price,
+        };
+        let shifts = YieldShift::grid(&parallel_shifts, &twist_shifts, pivot);
+        let scenarios = CtdAnalysis::scenarios(
+            extract_date(date)?,
+            future,
+            extract_basket(bonds, bond_ytms)?,
+            capital_rate,
+            reinvest_rate,
+            &shifts,
+        )?;
+        Ok(scenarios
+            .into_iter()
+            .map(|s| {
+                let ctd = s.ctd().clone();
+                (s.shift.parallel, s.shift.twist, s.future_price, Self(ctd))
+            })
+            .collect())
+    }
+
+    /// 交割期权(品质期权)估值
+    ///
+    /// 返回(交割期权价值, 期货理论价格, 期权调整期货DV01, [(债券代码, 期权调整净基差, 成为CTD的概率)]),
+    /// 债券按IRR从高到低排列
+    #[staticmethod]
+    #[pyo3(signature = (future, bonds, bond_ytms, date, future_price, capital_rate, vol, model="normal", reinvest_rate=None))]
+    pub fn delivery_option(
+        future: &Bound<'_, PyAny>,
+        bonds: Vec<Bound<'_, PyAny>>,
+        bond_ytms: Vec<f64>,
+        date: &Bound<'_, PyAny>,
+        future_price: f64,
+        capital_rate: f64,
+        vol: f64,
+        model: &str,
+        reinvest_rate: Option<f64>,
+    ) -> PyResult<(f64, f64, f64, Vec<(String, f64, f64)>)> {
+        let future = FuturePrice {
+            future: get_future(future)?.0,
+            price: future_price,
+        };
+        let analysis = CtdAnalysis::new(
+            extract_date(date)?,
+            future,
+            extract_basket(bonds, bond_ytms)?,
+            capital_rate,
+            reinvest_rate,
+        )?;
+        let option = analysis.delivery_option(model.parse()?, vol)?;
+        let bonds = analysis
+            .evaluators
+            .iter()
+            .zip(option.oa_net_basis_spreads)
+            .zip(option.ctd_probabilities)
+            .map(|((e, oa), p)| (e.bond.code().to_string(), oa, p))
+            .collect();
+        Ok((
+            option.option_value,
+            option.theoretical_price,
+            option.oa_future_dv01,
+            bonds,
+        ))
+    }
+
     fn copy(&self) -> Self {
         self.clone()
     }
@@ -282,6 +409,37 @@ impl PyTfEvaluator {
             .map(Self)
     }
 
+    /// 计算给定期货价格对应的隐含回购利率, 与IRR的计算口径一致
+    fn implied_repo(&self, future_price: f64) -> PyResult<f64> {
+        self.0
+            .clone()
+            .implied_repo(future_price)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// 计算期货理论价格, 即IRR等于回购利率时的期货价格
+    fn theoretical_price(&self, repo_rate: f64) -> PyResult<f64> {
+        self.0
+            .clone()
+            .theoretical_price(repo_rate)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// 按交割意向申报日为intention_date的滚动交割重新计算, 转换因子保持不变
+    fn early_delivery(&self, intention_date: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self(
+            self.0
+                .clone()
+                .early_delivery(extract_date(intention_date)?)?,
+        ))
+    }
+
+    #[pyo3(signature = (contracts=1.))]
+    /// 计算交割货款, 即期货全价(发票价格) * 合约乘数 * 交割手数
+    fn invoice_amount(&self, contracts: f64) -> PyResult<f64> {
+        Ok(self.0.clone().invoice_amount(contracts)?)
+    }
+
     /// 计算期货隐含收益率
     #[pyo3(signature = (use_deliver_date=true))]
     fn with_future_ytm(&self, use_deliver_date: bool) -> PyResult<Self> {
@@ -642,6 +800,63 @@ impl PyTfEvaluator {
             .neutral_net_basis_spread(ctd)
             .map_err(|e| PyValueError::new_err(e.to_string()))
     }
+
+    #[pyo3(signature = (face_value, ctd_bond=None, ctd_ytm=f64::NAN, beta=None))]
+    /// 计算债券头寸的期货套保手数, 正数表示卖出期货
+    ///
+    /// 返回(DV01中性手数, 转换因子比率手数, 回归beta手数)
+    ///
+    /// Args:
+    ///     face_value: 债券头寸面值(元)
+    ///     ctd_bond: 计算期货DV01使用的CTD债券, 如果为None则使用当前债券
+    ///     ctd_ytm: CTD债券收益率
+    ///     beta: 债券价格变动对期货价格变动的回归系数, 为None时不计算回归beta手数
+    fn hedge_contracts(
+        &self,
+        face_value: f64,
+        ctd_bond: Option<&Bound<'_, PyAny>>,
+        ctd_ytm: f64,
+        beta: Option<f64>,
+    ) -> PyResult<(f64, f64, Option<f64>)> {
+        let ctd = ctd_bond
+            .map(|bond| Ok::<_, PyErr>(BondYtm::new(get_bond(bond)?.0, ctd_ytm)))
+            .transpose()?;
+        let hedge = self
+            .0
+            .clone()
+            .hedge_contracts(face_value, ctd, beta)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok((hedge.dv01, hedge.cf, hedge.beta))
+    }
+
+    #[staticmethod]
+    /// 由债券价格和期货价格序列估计回归beta, 即债券价格变动对期货价格变动的OLS斜率
+    fn regression_beta(bond_prices: Vec<f64>, future_prices: Vec<f64>) -> PyResult<f64> {
+        regression_beta(&bond_prices, &future_prices)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    #[staticmethod]
+    /// 跨期价差分析, 近月和远月合约分别以各自的CTD计算
+    ///
+    /// 返回(市场价差, 理论价差, 隐含远期回购利率), 价差为近月价格 - 远月价格
+    ///
+    /// Args:
+    ///     near: 近月合约及其CTD的期现分析
+    ///     far: 远月合约及其CTD的期现分析
+    ///     repo_rate: 计算理论价差使用的回购利率
+    fn calendar_spread(
+        near: PyRef<'_, Self>,
+        far: PyRef<'_, Self>,
+        repo_rate: f64,
+    ) -> PyResult<(f64, f64, f64)> {
+        let spread = CalendarSpread::new(near.0.clone(), far.0.clone())?;
+        Ok((
+            spread.spread(),
+            spread.theoretical_spread(repo_rate)?,
+            spread.implied_forward_repo()?,
+        ))
+    }
 }
 
 #[cfg(test)]
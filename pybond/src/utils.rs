@@ -48,7 +48,8 @@ pub fn get_future(future: &Bound<'_, PyAny>) -> PyResult<PyFuture> {
     if let Ok(future) = future.extract::<PyFuture>() {
         Ok(future)
     } else if let Ok(future_str) = future.extract::<Cow<'_, str>>() {
-        let future_rs = Future::new(future_str);
+        let future_rs =
+            Future::parse(&future_str).map_err(|e| PyValueError::new_err(e.to_string()))?;
         Ok(PyFuture(Arc::new(future_rs)))
     } else {
         Err(PyValueError::new_err(
@@ -0,0 +1,337 @@
+use super::{Bond, CouponType};
+use crate::utils::month_delta;
+use anyhow::{Result, ensure};
+use chrono::{Months, NaiveDate};
+use parking_lot::RwLock;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+use tea_calendar::Calendar;
+
+/// 单笔现金流
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CashFlow {
+    pub nominal_date: NaiveDate,  // 名义付息日(未经节假日调整)
+    pub payment_date: NaiveDate,  // 实际支付日(按市场日历顺延至工作日)
+    pub accrual_start: NaiveDate, // 计息起始日
+    pub accrual_end: NaiveDate,   // 计息截止日
+    pub coupon: f64,              // 利息金额
+    pub principal: f64,           // 本金金额
+}
+
+impl CashFlow {
+    #[inline]
+    /// 现金流总额 = 利息 + 本金
+    pub fn amount(&self) -> f64 {
+        self.coupon + self.principal
+    }
+}
+
+/// 债券现金流计划, 按名义付息日升序排列
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CashFlowSchedule(Vec<CashFlow>);
+
+impl Deref for CashFlowSchedule {
+    type Target = [CashFlow];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<CashFlow>> for CashFlowSchedule {
+    #[inline]
+    fn from(mut flows: Vec<CashFlow>) -> Self {
+        flows.sort_by_key(|cf| cf.nominal_date);
+        Self(flows)
+    }
+}
+
+impl IntoIterator for CashFlowSchedule {
+    type Item = CashFlow;
+    type IntoIter = std::vec::IntoIter<CashFlow>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl CashFlowSchedule {
+    #[inline]
+    pub fn into_vec(self) -> Vec<CashFlow> {
+        self.0
+    }
+
+    /// 名义付息日晚于计算日的现金流
+    #[inline]
+    pub fn remain(&self, date: NaiveDate) -> &[CashFlow] {
+        let idx = self.partition_point(|cf| cf.nominal_date <= date);
+        &self[idx..]
+    }
+
+    /// 名义付息日不早于`from`且不晚于`until`的现金流
+    #[inline]
+    pub fn between(&self, from: NaiveDate, until: NaiveDate) -> &[CashFlow] {
+        let start = self.partition_point(|cf| cf.nominal_date < from);
+        let end = self.partition_point(|cf| cf.nominal_date <= until);
+        if start >= end { &[] } else { &self[start..end] }
+    }
+
+    /// 实际支付日位于开区间(from_exclusive, to_exclusive)内的现金流
+    #[inline]
+    pub fn paid_between(
+        &self,
+        from_exclusive: NaiveDate,
+        to_exclusive: NaiveDate,
+    ) -> impl Iterator<Item = &CashFlow> {
+        self.iter()
+            .filter(move |cf| cf.payment_date > from_exclusive && cf.payment_date < to_exclusive)
+    }
+
+    /// 计算日所在的计息区间[accrual_start, accrual_end)对应的现金流
+    #[inline]
+    pub fn accrual_period(&self, date: NaiveDate) -> Option<&CashFlow> {
+        self.iter()
+            .find(|cf| date >= cf.accrual_start && date < cf.accrual_end)
+    }
+}
+
+/// 债券现金流计划的缓存
+///
+/// 以生成现金流计划所依赖字段的哈希作为校验, 修改债券条款后缓存自动失效
+#[derive(Default)]
+pub(crate) struct ScheduleCache(RwLock<Option<(u64, Arc<CashFlowSchedule>)>>);
+
+impl Clone for ScheduleCache {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(RwLock::new(self.0.read().clone()))
+    }
+}
+
+impl std::fmt::Debug for ScheduleCache {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ScheduleCache")
+    }
+}
+
+impl Bond {
+    /// 生成现金流计划所依赖字段的哈希
+    fn schedule_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        std::mem::discriminant(&self.mkt).hash(&mut hasher);
+        std::mem::discriminant(&self.cp_type).hash(&mut hasher);
+        self.carry_date.hash(&mut hasher);
+        self.maturity_date.hash(&mut hasher);
+        self.inst_freq.hash(&mut hasher);
+        self.par_value.to_bits().hash(&mut hasher);
+        self.cp_rate.to_bits().hash(&mut hasher);
+        for step in &self.cp_rate_schedule {
+            step.start_date.hash(&mut hasher);
+            step.rate.to_bits().hash(&mut hasher);
+        }
+        for repayment in &self.amortization {
+            repayment.date.hash(&mut hasher);
+            repayment.ratio.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// 第n个名义付息日, 始终以起息日为锚点推算, 避免月末日期逐期漂移
+    #[inline]
+    pub(crate) fn nth_cp_date(&self, n: u32, offset: Months) -> NaiveDate {
+        self.carry_date + Months::new(offset.as_u32() * n)
+    }
+
+    #[inline]
+    fn new_cash_flow(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        coupon: f64,
+        principal: f64,
+    ) -> CashFlow {
+        CashFlow {
+            nominal_date: end,
+            payment_date: self.mkt.find_workday(end, 0),
+            accrual_start: start,
+            accrual_end: end,
+            coupon,
+            principal,
+        }
+    }
+
+    /// 按付息周期生成附息债券的现金流
+    ///
+    /// coupon根据计息区间的起止日给出该期的利息金额
+    pub(crate) fn coupon_bear_flows<F>(&self, mut coupon: F) -> Result<Vec<CashFlow>>
+    where
+        F: FnMut(NaiveDate, NaiveDate) -> Result<f64>,
+    {
+        let offset = self.get_cp_offset()?;
+        ensure!(offset.as_u32() > 0, "Invalid inst_freq: {}", self.inst_freq);
+        let mut flows = Vec::with_capacity(
+            (month_delta(self.carry_date, self.maturity_date) as u32 / offset.as_u32()) as usize
+                + 1,
+        );
+        let mut start = self.carry_date;
+        for n in 1.. {
+            let end = self.nth_cp_date(n, offset).min(self.maturity_date);
+            let is_last = end >= self.maturity_date;
+            // 分期还本债券在付息日偿还部分本金, 到期日偿还全部剩余本金
+            let principal = if is_last {
+                self.remaining_par(start)
+            } else {
+                self.remaining_par(start) - self.remaining_par(end)
+            };
+            flows.push(self.new_cash_flow(start, end, coupon(start, end)?, principal));
+            if is_last {
+                break;
+            }
+            start = end;
+        }
+        Ok(flows)
+    }
+
+    /// 生成债券完整的现金流计划
+    ///
+    /// 附息债券按付息频率从起息日推算名义付息日, 各期按适用的票面利率及期初剩余本金付息,
+    /// 在到期日偿还本金, 分期还本债券则按还本计划在各付息日偿还部分本金;
+    /// 到期一次还本付息债券在到期日一次性支付全部利息与本金;
+    /// 贴现债券仅在到期日偿还面值
+    ///
+    /// 现金流计划只生成一次并缓存在债券上, 债券条款修改后会重新生成;
+    /// 浮动利率债券的票息取决于定盘利率和远期曲线, 需使用`floating_cash_flow_schedule`
+    pub fn cash_flow_schedule(&self) -> Result<Arc<CashFlowSchedule>> {
+        self.ensure_fixed_rate()?;
+        let key = self.schedule_key();
+        if let Some((_, schedule)) = self
+            .schedule_cache
+            .0
+            .read()
+            .as_ref()
+            .filter(|(k, _)| *k == key)
+        {
+            return Ok(schedule.clone());
+        }
+        let schedule = Arc::new(self.build_cash_flow_schedule()?);
+        *self.schedule_cache.0.write() = Some((key, schedule.clone()));
+        Ok(schedule)
+    }
+
+    fn build_cash_flow_schedule(&self) -> Result<CashFlowSchedule> {
+        ensure!(
+            self.maturity_date > self.carry_date,
+            "Bond {} maturity date {} should be later than carry date {}",
+            self.code(),
+            self.maturity_date,
+            self.carry_date
+        );
+        self.check_amortization()?;
+        let flows = match self.cp_type {
+            CouponType::ZeroCoupon => {
+                vec![self.new_cash_flow(self.carry_date, self.maturity_date, 0., self.par_value)]
+            }
+            CouponType::OneTime => {
+                // 逐年按当年适用的票面利率累计利息, 不足一年的部分按月份折算
+                let months = month_delta(self.carry_date, self.maturity_date).max(0) as u32;
+                let coupon = (0..months.div_ceil(12))
+                    .map(|k| {
+                        let start = self.carry_date + Months::new(12 * k);
+                        let year = (months - 12 * k).min(12) as f64 / 12.;
+                        self.cp_rate_at(start) * self.par_value * year
+                    })
+                    .sum();
+                vec![self.new_cash_flow(
+                    self.carry_date,
+                    self.maturity_date,
+                    coupon,
+                    self.par_value,
+                )]
+            }
+            CouponType::CouponBear => {
+                self.coupon_bear_flows(|start, _| Ok(self.period_coupon(start)))?
+            }
+        };
+        Ok(CashFlowSchedule(flows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Market;
+
+    #[test]
+    fn test_cash_flow_schedule() {
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.03,
+            carry_date: NaiveDate::from_ymd_opt(2024, 8, 31).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2027, 8, 31).unwrap(),
+            inst_freq: 2,
+            ..Default::default()
+        };
+        let schedule = bond.cash_flow_schedule().unwrap();
+        assert_eq!(schedule.len(), 6);
+        // 以起息日为锚点, 月末日期不会逐期漂移
+        assert_eq!(
+            schedule[0].nominal_date,
+            NaiveDate::from_ymd_opt(2025, 2, 28).unwrap()
+        );
+        assert_eq!(
+            schedule[1].nominal_date,
+            NaiveDate::from_ymd_opt(2025, 8, 31).unwrap()
+        );
+        assert_eq!(schedule[1].accrual_start, schedule[0].accrual_end);
+        // 2025-08-31为周日, 顺延至下一工作日支付
+        assert_eq!(
+            schedule[1].payment_date,
+            NaiveDate::from_ymd_opt(2025, 9, 1).unwrap()
+        );
+        assert!(schedule[..5].iter().all(|cf| cf.principal == 0.));
+        assert_eq!(schedule[5].amount(), 101.5);
+        assert_eq!(schedule.iter().map(|cf| cf.coupon).sum::<f64>(), 9.);
+
+        let date = NaiveDate::from_ymd_opt(2025, 8, 31).unwrap();
+        assert_eq!(schedule.remain(date).len(), 4);
+        assert_eq!(
+            schedule.accrual_period(date).unwrap().accrual_end,
+            NaiveDate::from_ymd_opt(2026, 2, 28).unwrap()
+        );
+        assert_eq!(
+            schedule
+                .between(date, NaiveDate::from_ymd_opt(2026, 8, 31).unwrap())
+                .len(),
+            3
+        );
+        assert_eq!(
+            schedule
+                .paid_between(date, date.succ_opt().unwrap())
+                .count(),
+            0
+        );
+        let next = NaiveDate::from_ymd_opt(2025, 9, 2).unwrap();
+        assert_eq!(schedule.paid_between(date, next).count(), 1);
+
+        // 现金流计划只生成一次, 修改条款后重新生成
+        assert!(Arc::ptr_eq(&schedule, &bond.cash_flow_schedule().unwrap()));
+        let mut bond = bond;
+        bond.cp_rate = 0.04;
+        assert_eq!(bond.cash_flow_schedule().unwrap()[5].amount(), 102.);
+        bond.cp_rate = 0.03;
+
+        // 到期一次还本付息
+        let bond = Bond {
+            cp_type: CouponType::OneTime,
+            ..bond
+        };
+        let schedule = bond.cash_flow_schedule().unwrap();
+        assert_eq!(schedule.len(), 1);
+        assert!((schedule[0].coupon - 9.).abs() < 1e-10);
+        assert_eq!(schedule[0].principal, 100.);
+    }
+}
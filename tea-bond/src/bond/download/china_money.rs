@@ -130,6 +130,10 @@ impl Bond {
                 maturity_date: info["mrtyDate"].as_str().unwrap().parse().unwrap(),
                 day_count: BondDayCount::default(),
                 issue_price: None,
+                cp_rate_schedule: Vec::new(),
+                amortization: Vec::new(),
+                embedded_options: Vec::new(),
+                schedule_cache: Default::default(),
             };
             if bond.cp_rate != 0. {
                 assert!(bond.cp_type != CouponType::ZeroCoupon);
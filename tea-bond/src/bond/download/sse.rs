@@ -140,6 +140,10 @@ impl Bond {
                     )?,
                     day_count: BondDayCount::default(),
                     issue_price: None,
+                    cp_rate_schedule: Vec::new(),
+                    amortization: Vec::new(),
+                    embedded_options: Vec::new(),
+                    schedule_cache: Default::default(),
                 };
                 return Ok(bond);
             }
@@ -0,0 +1,289 @@
+use super::{Bond, CouponType, OptionType, RemainCashFlows, deserialize_date, serialize_date};
+use crate::YieldSolver;
+use anyhow::{Result, bail, ensure};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+const fn default_exercise_price() -> f64 {
+    100.0
+}
+
+/// 含权债券的行权条款
+///
+/// 票面利率调整权在行权日后生效, 调整后的票面利率可通过cp_rate_schedule设置
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedOption {
+    pub option_type: OptionType, // 赎回或回售
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub exercise_date: NaiveDate, // 行权日, 应为付息日
+    #[serde(default = "default_exercise_price")]
+    pub exercise_price: f64, // 行权价格, 每百元剩余本金, 不含当期利息
+}
+
+impl EmbeddedOption {
+    #[inline]
+    pub fn new(option_type: OptionType, exercise_date: NaiveDate, exercise_price: f64) -> Self {
+        Self {
+            option_type,
+            exercise_date,
+            exercise_price,
+        }
+    }
+
+    #[inline]
+    pub fn call(exercise_date: NaiveDate) -> Self {
+        Self::new(OptionType::Call, exercise_date, default_exercise_price())
+    }
+
+    #[inline]
+    pub fn put(exercise_date: NaiveDate) -> Self {
+        Self::new(OptionType::Put, exercise_date, default_exercise_price())
+    }
+}
+
+impl Bond {
+    #[inline]
+    /// 是否为含权债券
+    pub fn has_embedded_option(&self) -> bool {
+        !self.embedded_options.is_empty()
+    }
+
+    /// 计算日之后最近一次指定类型的行权条款
+    pub fn next_exercise(
+        &self,
+        date: NaiveDate,
+        option_type: OptionType,
+    ) -> Option<&EmbeddedOption> {
+        self.embedded_options
+            .iter()
+            .filter(|o| o.option_type == option_type && o.exercise_date > date)
+            .min_by_key(|o| o.exercise_date)
+    }
+
+    /// 假设在行权日行权时的剩余现金流
+    ///
+    /// 返回以行权日为到期日的债券, 以及行权日按行权价格偿还剩余本金后的剩余现金流
+    fn exercise_cash_flows(
+        &self,
+        date: NaiveDate,
+        option: &EmbeddedOption,
+    ) -> Result<(Bond, RemainCashFlows)> {
+        ensure!(
+            self.cp_type == CouponType::CouponBear,
+            "Bond {} with embedded option should be a coupon bearing bond",
+            self.code()
+        );
+        ensure!(
+            option.exercise_date > date && option.exercise_date <= self.maturity_date,
+            "Exercise date {} of bond {} should be within ({}, {}]",
+            option.exercise_date,
+            self.code(),
+            date,
+            self.maturity_date
+        );
+        ensure!(
+            self.cash_flow_schedule()?
+                .iter()
+                .any(|cf| cf.nominal_date == option.exercise_date),
+            "Exercise date {} of bond {} is not a coupon date",
+            option.exercise_date,
+            self.code()
+        );
+        let bond = Bond {
+            maturity_date: option.exercise_date,
+            amortization: self
+                .amortization
+                .iter()
+                .filter(|r| r.date <= option.exercise_date)
+                .copied()
+                .collect(),
+            ..self.clone()
+        };
+        let mut remain = bond.remain_cash_flows(date, None, None)?;
+        if let Some((cf, _)) = remain.flows.last_mut() {
+            cf.principal *= option.exercise_price / 100.;
+        }
+        Ok((bond, remain))
+    }
+
+    /// 通过全价计算行权收益率
+    pub fn calc_ytm_to_exercise(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        option: &EmbeddedOption,
+    ) -> Result<f64> {
+        let (bond, remain) = self.exercise_cash_flows(date, option)?;
+        bond.ytm_with_remain_flows(dirty_price, date, remain, &YieldSolver::default())
+    }
+
+    /// 通过行权收益率计算全价
+    pub fn calc_dirty_price_to_exercise(
+        &self,
+        ytm: f64,
+        date: NaiveDate,
+        option: &EmbeddedOption,
+    ) -> Result<f64> {
+        let ytm = self.check_ytm(ytm);
+        let (bond, remain) = self.exercise_cash_flows(date, option)?;
+        bond.dirty_price_with_remain_flows(ytm, date, remain)
+    }
+
+    /// 行权修正久期
+    pub fn calc_duration_to_exercise(
+        &self,
+        ytm: f64,
+        date: NaiveDate,
+        option: &EmbeddedOption,
+    ) -> Result<f64> {
+        let ytm = self.check_ytm(ytm);
+        let (bond, remain) = self.exercise_cash_flows(date, option)?;
+        let duration = bond.macaulay_duration_with_remain_flows(ytm, remain);
+        Ok(duration / (1. + ytm / self.inst_freq as f64))
+    }
+
+    fn next_exercise_or_err(
+        &self,
+        date: NaiveDate,
+        option_type: OptionType,
+    ) -> Result<&EmbeddedOption> {
+        match self.next_exercise(date, option_type) {
+            Some(option) => Ok(option),
+            None => bail!(
+                "Bond {} has no {:?} option after {}",
+                self.code(),
+                option_type,
+                date
+            ),
+        }
+    }
+
+    /// 通过全价计算到下一赎回日的收益率(yield to call)
+    #[inline]
+    pub fn calc_ytc(&self, dirty_price: f64, date: NaiveDate) -> Result<f64> {
+        let option = self.next_exercise_or_err(date, OptionType::Call)?;
+        self.calc_ytm_to_exercise(dirty_price, date, option)
+    }
+
+    /// 通过全价计算到下一回售日的收益率(yield to put)
+    #[inline]
+    pub fn calc_ytp(&self, dirty_price: f64, date: NaiveDate) -> Result<f64> {
+        let option = self.next_exercise_or_err(date, OptionType::Put)?;
+        self.calc_ytm_to_exercise(dirty_price, date, option)
+    }
+
+    /// 到下一赎回日的修正久期
+    #[inline]
+    pub fn calc_ytc_duration(&self, ytc: f64, date: NaiveDate) -> Result<f64> {
+        let option = self.next_exercise_or_err(date, OptionType::Call)?;
+        self.calc_duration_to_exercise(ytc, date, option)
+    }
+
+    /// 到下一回售日的修正久期
+    #[inline]
+    pub fn calc_ytp_duration(&self, ytp: f64, date: NaiveDate) -> Result<f64> {
+        let option = self.next_exercise_or_err(date, OptionType::Put)?;
+        self.calc_duration_to_exercise(ytp, date, option)
+    }
+
+    /// 最差收益率及对应的行权条款, 行权条款为None表示持有至到期
+    fn worst_workout(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+    ) -> Result<(f64, Option<EmbeddedOption>)> {
+        let mut worst = (
+            self.calc_ytm_with_price(dirty_price, date, None, None)?,
+            None,
+        );
+        for option in self
+            .embedded_options
+            .iter()
+            .filter(|o| o.option_type == OptionType::Call && o.exercise_date > date)
+        {
+            let ytc = self.calc_ytm_to_exercise(dirty_price, date, option)?;
+            if ytc < worst.0 {
+                worst = (ytc, Some(*option));
+            }
+        }
+        Ok(worst)
+    }
+
+    /// 通过全价计算最差收益率(yield to worst)
+    ///
+    /// 取到期收益率与各赎回日收益率中的最小值, 回售权由投资者决定是否行使, 不参与比较
+    #[inline]
+    pub fn calc_ytw(&self, dirty_price: f64, date: NaiveDate) -> Result<f64> {
+        Ok(self.worst_workout(dirty_price, date)?.0)
+    }
+
+    /// 最差收益率对应行权路径的修正久期
+    pub fn calc_ytw_duration(&self, dirty_price: f64, date: NaiveDate) -> Result<f64> {
+        match self.worst_workout(dirty_price, date)? {
+            (ytw, Some(option)) => self.calc_duration_to_exercise(ytw, date, &option),
+            (ytw, None) => self.calc_duration(ytw, date, None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Market;
+
+    #[test]
+    fn test_embedded_option() {
+        // 3+2年期, 第3年末附发行人赎回权及投资者回售权
+        let exercise_date = NaiveDate::from_ymd_opt(2026, 6, 20).unwrap();
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.035,
+            inst_freq: 1,
+            carry_date: NaiveDate::from_ymd_opt(2023, 6, 20).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2028, 6, 20).unwrap(),
+            embedded_options: vec![
+                EmbeddedOption::call(exercise_date),
+                EmbeddedOption::put(exercise_date),
+            ],
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 3, 20).unwrap();
+        let option = bond.next_exercise(date, OptionType::Put).unwrap();
+        assert_eq!(option.exercise_date, exercise_date);
+        let price = bond
+            .calc_dirty_price_to_exercise(0.03, date, option)
+            .unwrap();
+        // 行权前仅剩两次付息, 按行权日偿还本金
+        let t = 92. / 365.;
+        let expect = 3.5 / 1.03f64.powf(t) + 103.5 / 1.03f64.powf(t + 1.);
+        assert!((price - expect).abs() < 1e-10);
+        assert!((bond.calc_ytp(price, date).unwrap() - 0.03).abs() < 1e-8);
+        assert!((bond.calc_ytc(price, date).unwrap() - 0.03).abs() < 1e-8);
+        // 溢价债券的最差收益率为赎回收益率, 低于到期收益率
+        let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+        let ytw = bond.calc_ytw(price, date).unwrap();
+        assert!(ytm > ytw && (ytw - 0.03).abs() < 1e-8);
+        let duration = bond.calc_ytc_duration(0.03, date).unwrap();
+        assert!((bond.calc_ytw_duration(price, date).unwrap() - duration).abs() < 1e-8);
+        assert!(duration < bond.calc_duration(ytm, date, None, None).unwrap());
+        // 折价债券持有至到期为最差路径
+        let price = bond
+            .calc_dirty_price_with_ytm(0.05, date, None, None)
+            .unwrap();
+        assert!((bond.calc_ytw(price, date).unwrap() - 0.05).abs() < 1e-8);
+
+        // 行权价格高于面值
+        let premium = EmbeddedOption::new(OptionType::Call, exercise_date, 101.);
+        let price = bond
+            .calc_dirty_price_to_exercise(0.03, date, &premium)
+            .unwrap();
+        assert!((price - expect - 1. / 1.03f64.powf(t + 1.)).abs() < 1e-10);
+        // 行权日之后没有赎回权
+        assert!(bond.calc_ytc(price, exercise_date).is_err());
+        let invalid = EmbeddedOption::call(NaiveDate::from_ymd_opt(2026, 9, 20).unwrap());
+        assert!(bond.calc_ytm_to_exercise(price, date, &invalid).is_err());
+    }
+}
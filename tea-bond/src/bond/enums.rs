@@ -54,6 +54,25 @@ impl FromStr for InterestType {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum OptionType {
+    /// 发行人赎回
+    Call,
+    /// 投资者回售
+    Put,
+}
+
+impl FromStr for OptionType {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Call" | "call" | "赎回" => Ok(OptionType::Call),
+            "Put" | "put" | "回售" => Ok(OptionType::Put),
+            _ => anyhow::bail!("Unknown option type: {}", s),
+        }
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
 pub enum Market {
     /// 银行间
@@ -140,3 +159,34 @@ impl FromStr for BondDayCount {
         }
     }
 }
+
+impl BondDayCount {
+    /// 按计息基准计算两个日期之间的计息天数
+    #[inline]
+    pub fn count_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        use crate::day_counter::*;
+        match self {
+            BondDayCount::ActAct | BondDayCount::Act360 | BondDayCount::Act365F => {
+                ACTUAL.count_days(start, end)
+            }
+            BondDayCount::Act365 => ACTUAL_NO_LEAP.count_days(start, end),
+            BondDayCount::Thirty365 | BondDayCount::Thirty360 => THIRTY.count_days(start, end),
+            BondDayCount::Bus => BUSINESS.count_days(start, end),
+            BondDayCount::BusIB => IB_BUSINESS.count_days(start, end),
+            BondDayCount::BusSSE => SSE_BUSINESS.count_days(start, end),
+        }
+    }
+
+    /// 计息基准对应的年度天数
+    ///
+    /// ACT/ACT按实际付息周期计算, 没有固定的年度天数, 返回None
+    #[inline]
+    pub fn year_days(&self) -> Option<f64> {
+        match self {
+            BondDayCount::ActAct => None,
+            BondDayCount::Act365 | BondDayCount::Act365F | BondDayCount::Thirty365 => Some(365.),
+            BondDayCount::Act360 | BondDayCount::Thirty360 => Some(360.),
+            BondDayCount::Bus | BondDayCount::BusIB | BondDayCount::BusSSE => Some(252.),
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use super::{Bond, CashFlow, CashFlowSchedule, CouponType, InterestType};
+use crate::YieldSolver;
+use crate::curve::RateCurve;
+use anyhow::{Context, Result, bail, ensure};
+use chrono::NaiveDate;
+use tea_calendar::Calendar;
+
+/// 浮动利率债券基准利率的定盘序列, 如DR007/Shibor/LPR
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateFixings(Vec<(NaiveDate, f64)>);
+
+impl FromIterator<(NaiveDate, f64)> for RateFixings {
+    fn from_iter<I: IntoIterator<Item = (NaiveDate, f64)>>(iter: I) -> Self {
+        let mut fixings: Vec<_> = iter.into_iter().filter(|(_, r)| !r.is_nan()).collect();
+        fixings.sort_by_key(|(d, _)| *d);
+        Self(fixings)
+    }
+}
+
+impl RateFixings {
+    #[inline]
+    pub fn new(fixings: impl IntoIterator<Item = (NaiveDate, f64)>) -> Self {
+        fixings.into_iter().collect()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// 不晚于指定日期的最近一次定盘利率
+    #[inline]
+    pub fn fixing(&self, date: NaiveDate) -> Option<f64> {
+        let idx = self.0.partition_point(|(d, _)| *d <= date);
+        if idx == 0 {
+            None
+        } else {
+            Some(self.0[idx - 1].1)
+        }
+    }
+
+    /// 晚于after且不晚于date的最近一次定盘利率, 用于限制定盘利率的最长沿用期限
+    #[inline]
+    pub fn fixing_within(&self, after: NaiveDate, date: NaiveDate) -> Option<f64> {
+        let idx = self.0.partition_point(|(d, _)| *d <= date);
+        match idx.checked_sub(1).map(|i| self.0[i]) {
+            Some((d, rate)) if d > after => Some(rate),
+            _ => None,
+        }
+    }
+}
+
+/// 利差久期的计算步长, 1bp
+const SPREAD_BUMP: f64 = 1e-4;
+
+impl Bond {
+    #[inline]
+    /// 是否为浮动利率债券
+    pub fn is_floating(&self) -> bool {
+        self.interest_type == InterestType::Floating
+    }
+
+    #[inline]
+    /// 按票面利率定价的接口不适用于浮动利率债券, 浮动利率债券需通过定盘利率和利率曲线定价
+    pub(crate) fn ensure_fixed_rate(&self) -> Result<()> {
+        ensure!(
+            !self.is_floating(),
+            "Floating rate bond {} should be priced with fixings and a rate curve",
+            self.code()
+        );
+        Ok(())
+    }
+
+    #[inline]
+    /// 付息周期的基准利率重置日, 为计息起始日的前一个工作日
+    pub fn reset_date(&self, accrual_start: NaiveDate) -> NaiveDate {
+        self.mkt.find_workday(accrual_start, -1)
+    }
+
+    /// 付息周期适用的基准利率
+    ///
+    /// 重置日不晚于计算日时使用定盘利率, 否则使用曲线推算的远期利率;
+    /// 定盘利率最多沿用一个付息周期, 重置日前一个付息周期内没有定盘利率视为缺失,
+    /// 首期以及已付息的周期缺少定盘利率时使用发行时的基准利率`base_rate`
+    fn period_index_rate(
+        &self,
+        start: NaiveDate,
+        end: NaiveDate,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+    ) -> Result<f64> {
+        let reset_date = self.reset_date(start);
+        if reset_date > date {
+            return Ok(curve.forward_rate(start, end));
+        }
+        let lookback = reset_date - self.get_cp_offset()?;
+        if let Some(rate) = fixings.fixing_within(lookback, reset_date) {
+            Ok(rate)
+        } else if start == self.carry_date || end <= date {
+            self.base_rate.with_context(|| {
+                format!(
+                    "Missing fixing and base rate of bond {} for reset date {}",
+                    self.code(),
+                    reset_date
+                )
+            })
+        } else {
+            bail!(
+                "Missing fixing of bond {} for reset date {}",
+                self.code(),
+                reset_date
+            )
+        }
+    }
+
+    /// 浮动利率债券的现金流及各期适用的基准利率
+    fn floating_flows(
+        &self,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+    ) -> Result<Vec<(CashFlow, f64)>> {
+        ensure!(
+            self.is_floating(),
+            "Bond {} is not a floating rate bond",
+            self.code()
+        );
+        ensure!(
+            self.cp_type == CouponType::CouponBear,
+            "Floating rate bond {} should be a coupon bearing bond",
+            self.code()
+        );
+        self.check_amortization()?;
+        let spread = self.rate_spread.unwrap_or(0.);
+        let mut index_rates = vec![];
+        let flows = self.coupon_bear_flows(|start, end| {
+            let rate = self.period_index_rate(start, end, date, fixings, curve)?;
+            index_rates.push(rate);
+            Ok((rate + spread) * self.remaining_par(start) / self.inst_freq as f64)
+        })?;
+        Ok(flows.into_iter().zip(index_rates).collect())
+    }
+
+    /// 浮动利率债券的现金流计划
+    ///
+    /// 票面利率 = 基准利率 + 固定利差, 已重置的付息周期使用定盘利率, 之后的付息周期使用远期利率
+    pub fn floating_cash_flow_schedule(
+        &self,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+    ) -> Result<CashFlowSchedule> {
+        let flows = self.floating_flows(date, fixings, curve)?;
+        Ok(flows
+            .into_iter()
+            .map(|(cf, _)| cf)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    /// 浮动利率债券的应计利息, 按当期重置后的票面利息计提
+    pub fn calc_frn_accrued_interest(
+        &self,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+    ) -> Result<f64> {
+        let flows = self.floating_flows(date, fixings, curve)?;
+        let Some((cf, _)) = flows
+            .iter()
+            .find(|(cf, _)| date >= cf.accrual_start && date < cf.accrual_end)
+        else {
+            return Ok(0.);
+        };
+        let (pre_cp_date, next_cp_date) = (cf.accrual_start, cf.accrual_end);
+        Ok(match self.day_count.year_days() {
+            Some(year_days) => {
+                cf.coupon
+                    * self.inst_freq as f64
+                    * self.day_count.count_days(pre_cp_date, date) as f64
+                    / year_days
+            }
+            None => {
+                cf.coupon * (date - pre_cp_date).num_days() as f64
+                    / (next_cp_date - pre_cp_date).num_days() as f64
+            }
+        })
+    }
+
+    /// 通过贴现利差(discount margin)计算浮动利率债券全价
+    ///
+    /// 当期现金流按当期基准利率加贴现利差折现, 之后各期依次按远期利率加贴现利差折现
+    pub fn calc_frn_dirty_price(
+        &self,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+        discount_margin: f64,
+    ) -> Result<f64> {
+        let flows = self.floating_flows(date, fixings, curve)?;
+        let mut discount_factor = 1.;
+        let mut pv = 0.;
+        for (cf, index_rate) in flows.iter().filter(|(cf, _)| cf.nominal_date > date) {
+            let tau = self.year_fraction(cf.accrual_start.max(date), cf.accrual_end);
+            discount_factor /= 1. + (index_rate + discount_margin) * tau;
+            pv += cf.amount() * discount_factor;
+        }
+        Ok(pv)
+    }
+
+    /// 通过浮动利率债券全价计算贴现利差
+    pub fn calc_discount_margin(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+    ) -> Result<f64> {
+        // 提前检查参数, 避免在求解过程中出错
+        self.floating_flows(date, fixings, curve)?;
+        let f = |dm: f64| {
+            self.calc_frn_dirty_price(date, fixings, curve, dm)
+                .unwrap_or(f64::NAN)
+                - dirty_price
+        };
+        YieldSolver::default()
+            .with_bracket(-0.05, 0.2)
+            .brent(f)
+            .with_context(|| format!("Failed to solve discount margin of bond {}", self.code()))
+    }
+
+    /// 利差久期
+    ///
+    /// 贴现利差变动1bp时全价的相对变动
+    pub fn calc_spread_duration(
+        &self,
+        date: NaiveDate,
+        fixings: &RateFixings,
+        curve: &impl RateCurve,
+        discount_margin: f64,
+    ) -> Result<f64> {
+        let price = self.calc_frn_dirty_price(date, fixings, curve, discount_margin)?;
+        let price_down =
+            self.calc_frn_dirty_price(date, fixings, curve, discount_margin - SPREAD_BUMP)?;
+        let price_up =
+            self.calc_frn_dirty_price(date, fixings, curve, discount_margin + SPREAD_BUMP)?;
+        Ok((price_down - price_up) / (2. * SPREAD_BUMP * price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Market;
+
+    /// 水平曲线, 即期利率为年复利
+    struct FlatCurve(NaiveDate, f64);
+
+    impl RateCurve for FlatCurve {
+        fn ref_date(&self) -> NaiveDate {
+            self.0
+        }
+
+        fn discount_factor_t(&self, t: f64) -> f64 {
+            (1. + self.1).powf(-t)
+        }
+    }
+
+    #[test]
+    fn test_floating_rate_bond() {
+        let bond = Bond {
+            mkt: Market::IB,
+            interest_type: InterestType::Floating,
+            cp_rate: 0.022,
+            base_rate: Some(0.02),
+            rate_spread: Some(0.002),
+            inst_freq: 4,
+            carry_date: NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2027, 3, 15).unwrap(),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 5, 20).unwrap();
+        let fixings = RateFixings::new([
+            (NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(), 0.02),
+            (NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(), 0.018),
+        ]);
+        let curve = FlatCurve(date, 0.016);
+        let schedule = bond
+            .floating_cash_flow_schedule(date, &fixings, &curve)
+            .unwrap();
+        assert_eq!(schedule.len(), 12);
+        // 当期按2025-03-14的定盘利率重置
+        let current = schedule.accrual_period(date).unwrap();
+        assert!((current.coupon - (0.018 + 0.002) * 100. / 4.).abs() < 1e-12);
+        // 未来各期按远期利率推算
+        let last = schedule.last().unwrap();
+        let fwd = curve.forward_rate(last.accrual_start, last.accrual_end);
+        assert!((last.coupon - (fwd + 0.002) * 25.).abs() < 1e-12);
+
+        let price = bond
+            .calc_frn_dirty_price(date, &fixings, &curve, 0.003)
+            .unwrap();
+        let dm = bond
+            .calc_discount_margin(price, date, &fixings, &curve)
+            .unwrap();
+        assert!((dm - 0.003).abs() < 1e-8);
+        // 贴现利差等于固定利差时价格接近面值加应计利息
+        let par_price = bond
+            .calc_frn_dirty_price(date, &fixings, &curve, 0.002)
+            .unwrap();
+        let accrued_interest = bond
+            .calc_frn_accrued_interest(date, &fixings, &curve)
+            .unwrap();
+        assert!((accrued_interest - 0.5 * 66. / 92.).abs() < 1e-12);
+        assert!((par_price - 100. - accrued_interest).abs() < 0.01);
+        let spread_duration = bond
+            .calc_spread_duration(date, &fixings, &curve, 0.003)
+            .unwrap();
+        assert!(spread_duration > 1.5 && spread_duration < 1.9);
+        // 按票面利率定价的接口不会把浮动利率债券当作固定利率债券定价
+        assert!(bond.cash_flow_schedule().is_err());
+        assert!(bond.calc_accrued_interest(date, None).is_err());
+        assert!(
+            bond.calc_ytm_with_price(par_price, date, None, None)
+                .is_err()
+        );
+        assert!(bond.calc_duration(0.02, date, None, None).is_err());
+
+        // 缺少当期定盘利率时报错, 9个月前的定盘利率超过一个付息周期, 不再沿用
+        let fixings = RateFixings::new([(NaiveDate::from_ymd_opt(2024, 3, 14).unwrap(), 0.02)]);
+        let later = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        assert!(
+            bond.floating_cash_flow_schedule(later, &RateFixings::default(), &curve)
+                .is_err()
+        );
+        assert!(
+            bond.floating_cash_flow_schedule(later, &fixings, &curve)
+                .is_err()
+        );
+        // 已付息的周期缺少定盘利率时使用发行时的基准利率, 未设置基准利率时报错
+        let no_base_rate = Bond {
+            base_rate: None,
+            ..bond.clone()
+        };
+        assert!(
+            no_base_rate
+                .floating_cash_flow_schedule(later, &fixings, &curve)
+                .is_err()
+        );
+        let fixings = RateFixings::new([(NaiveDate::from_ymd_opt(2024, 11, 29).unwrap(), 0.019)]);
+        let schedule = bond
+            .floating_cash_flow_schedule(later, &fixings, &curve)
+            .unwrap();
+        assert!((schedule[0].coupon - (0.02 + 0.002) * 25.).abs() < 1e-12);
+        assert!(
+            no_base_rate
+                .floating_cash_flow_schedule(later, &fixings, &curve)
+                .is_err()
+        );
+        // 重置日(2024-12-13)前一个付息周期内的定盘利率可以沿用
+        assert!((schedule.remain(later)[0].coupon - (0.019 + 0.002) * 25.).abs() < 1e-12);
+        assert_eq!(
+            fixings.fixing_within(NaiveDate::from_ymd_opt(2024, 9, 13).unwrap(), later),
+            Some(0.019)
+        );
+        assert_eq!(
+            fixings.fixing_within(NaiveDate::from_ymd_opt(2024, 11, 29).unwrap(), later),
+            None
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use super::{Bond, CashFlow};
+use crate::day_counter::{ACTUAL, DayCountRule};
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+
+impl Bond {
+    /// 计算日(不含)至远期结算日(含)之间发生的现金流
+    fn cash_flows_to_forward(
+        &self,
+        date: NaiveDate,
+        forward_date: NaiveDate,
+    ) -> Result<Vec<CashFlow>> {
+        ensure!(
+            forward_date > date,
+            "Forward date {} should be later than {}",
+            forward_date,
+            date
+        );
+        ensure!(
+            forward_date < self.maturity_date,
+            "Forward date {} should be earlier than maturity date {} of bond {}",
+            forward_date,
+            self.maturity_date,
+            self.code()
+        );
+        let schedule = self.cash_flow_schedule()?;
+        Ok(schedule
+            .remain(date)
+            .iter()
+            .take_while(|cf| cf.nominal_date <= forward_date)
+            .copied()
+            .collect())
+    }
+
+    /// 通过全价计算远期全价
+    ///
+    /// 以回购利率融资买入债券并持有至远期结算日, 期间现金流按再投资利率(默认为回购利率)单利再投资至远期结算日:
+    /// 远期全价 = 全价 * (1 + 回购利率 * 天数/365) - Σ 期间现金流 * (1 + 再投资利率 * 再投资天数/365)
+    ///
+    /// 分期还本债券按每百元剩余本金报价
+    pub fn calc_forward_dirty_price(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        forward_date: NaiveDate,
+        repo_rate: f64,
+        reinvest_rate: Option<f64>,
+    ) -> Result<f64> {
+        let flows = self.cash_flows_to_forward(date, forward_date)?;
+        let reinvest_rate = reinvest_rate.unwrap_or(repo_rate);
+        let days = ACTUAL.count_days(date, forward_date) as f64;
+        let financed =
+            dirty_price * self.remaining_par_factor(date) * (1. + repo_rate * days / 365.);
+        let reinvested: f64 = flows
+            .iter()
+            .map(|cf| {
+                let days = ACTUAL.count_days(cf.nominal_date, forward_date) as f64;
+                cf.amount() * (1. + reinvest_rate * days / 365.)
+            })
+            .sum();
+        Ok((financed - reinvested) / self.remaining_par_factor(forward_date))
+    }
+
+    /// 通过全价计算远期净价, 即远期全价减去远期结算日的应计利息
+    pub fn calc_forward_clean_price(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        forward_date: NaiveDate,
+        repo_rate: f64,
+        reinvest_rate: Option<f64>,
+    ) -> Result<f64> {
+        let forward_dirty_price = self.calc_forward_dirty_price(
+            dirty_price,
+            date,
+            forward_date,
+            repo_rate,
+            reinvest_rate,
+        )?;
+        Ok(forward_dirty_price - self.calc_accrued_interest(forward_date, None)?)
+    }
+
+    /// 通过全价和远期全价计算隐含回购利率
+    ///
+    /// 未指定再投资利率时期间现金流按隐含回购利率再投资, 与国债期货IRR的计算口径一致:
+    /// 隐含回购利率 = (远期全价 + 期间现金流 - 全价) / (全价 * 天数/365 - 加权平均期间现金流)
+    pub fn calc_implied_repo(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        forward_date: NaiveDate,
+        forward_dirty_price: f64,
+        reinvest_rate: Option<f64>,
+    ) -> Result<f64> {
+        let flows = self.cash_flows_to_forward(date, forward_date)?;
+        let dirty_price = dirty_price * self.remaining_par_factor(date);
+        let forward_dirty_price = forward_dirty_price * self.remaining_par_factor(forward_date);
+        let year = ACTUAL.count_days(date, forward_date) as f64 / 365.;
+        // 期间现金流及按再投资期限加权的期间现金流
+        let (cash, cash_wm) = flows.iter().fold((0., 0.), |(cash, wm), cf| {
+            let year = ACTUAL.count_days(cf.nominal_date, forward_date) as f64 / 365.;
+            (cash + cf.amount(), wm + cf.amount() * year)
+        });
+        match reinvest_rate {
+            Some(reinvest_rate) => Ok((forward_dirty_price + cash + cash_wm * reinvest_rate
+                - dirty_price)
+                / (dirty_price * year)),
+            None => Ok((forward_dirty_price + cash - dirty_price) / (dirty_price * year - cash_wm)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Market;
+
+    #[test]
+    fn test_forward_price() {
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.03,
+            inst_freq: 2,
+            carry_date: NaiveDate::from_ymd_opt(2023, 5, 15).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2033, 5, 15).unwrap(),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let forward_date = NaiveDate::from_ymd_opt(2025, 6, 3).unwrap();
+        let dirty_price = 104.;
+        // 期间收到2025-05-15的半年利息1.5
+        let fwd = bond
+            .calc_forward_dirty_price(dirty_price, date, forward_date, 0.018, None)
+            .unwrap();
+        let expect = 104. * (1. + 0.018 * 92. / 365.) - 1.5 * (1. + 0.018 * 19. / 365.);
+        assert!((fwd - expect).abs() < 1e-10);
+        // 隐含回购利率还原回购利率
+        let repo = bond
+            .calc_implied_repo(dirty_price, date, forward_date, fwd, None)
+            .unwrap();
+        assert!((repo - 0.018).abs() < 1e-12);
+        let fwd = bond
+            .calc_forward_dirty_price(dirty_price, date, forward_date, 0.018, Some(0.01))
+            .unwrap();
+        let repo = bond
+            .calc_implied_repo(dirty_price, date, forward_date, fwd, Some(0.01))
+            .unwrap();
+        assert!((repo - 0.018).abs() < 1e-12);
+        // 远期净价
+        let clean = bond
+            .calc_forward_clean_price(dirty_price, date, forward_date, 0.018, None)
+            .unwrap();
+        let ai = bond.calc_accrued_interest(forward_date, None).unwrap();
+        assert!(
+            (clean + ai
+                - bond
+                    .calc_forward_dirty_price(dirty_price, date, forward_date, 0.018, None)
+                    .unwrap())
+            .abs()
+                < 1e-12
+        );
+
+        // 期间无付息时远期全价仅包含融资成本
+        let forward_date = NaiveDate::from_ymd_opt(2025, 4, 3).unwrap();
+        let fwd = bond
+            .calc_forward_dirty_price(dirty_price, date, forward_date, 0.018, None)
+            .unwrap();
+        assert!((fwd - 104. * (1. + 0.018 * 31. / 365.)).abs() < 1e-10);
+        assert!(
+            bond.calc_forward_dirty_price(dirty_price, forward_date, date, 0.018, None)
+                .is_err()
+        );
+    }
+}
@@ -22,6 +22,10 @@ impl Default for Bond {
             maturity_date: NaiveDate::default(),
             day_count: BondDayCount::default(),
             issue_price: None,
+            cp_rate_schedule: Vec::new(),
+            amortization: Vec::new(),
+            embedded_options: Vec::new(),
+            schedule_cache: Default::default(),
         }
     }
 }
@@ -30,7 +34,7 @@ impl Default for Bond {
 /// 将字符串转换为日期
 ///
 /// 仅用于从json文件反序列化日期
-pub(super) fn deserialize_date<'de, D>(deserializer: D) -> std::result::Result<NaiveDate, D::Error>
+pub(crate) fn deserialize_date<'de, D>(deserializer: D) -> std::result::Result<NaiveDate, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -39,7 +43,7 @@ where
 }
 
 #[inline]
-pub(super) fn serialize_date<S>(
+pub(crate) fn serialize_date<S>(
     date: &NaiveDate,
     serializer: S,
 ) -> std::result::Result<S::Ok, S::Error>
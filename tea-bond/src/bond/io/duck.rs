@@ -14,6 +14,19 @@ pub static DUCKDB_TABLE_PATH: LazyLock<String> = LazyLock::new(|| {
     })
 });
 
+const BOND_INFO_COLUMNS: &str = "s_info_windcode, s_info_name, b_info_par, b_info_coupon, b_info_interesttype, b_info_couponrate, b_info_spread, b_info_interestfrequency, b_info_carrydate, b_info_maturitydate, b_tendrst_referyield, b_info_issueprice";
+
+#[inline]
+fn check_table_name(table: &str) -> Result<()> {
+    if table
+        .chars()
+        .any(|c| !c.is_ascii_alphanumeric() && c != '_')
+    {
+        bail!("Invalid table name: {table}");
+    }
+    Ok(())
+}
+
 impl TryFrom<&Row<'_>> for WindSqlRow {
     type Error = duckdb::Error;
     fn try_from(value: &Row<'_>) -> std::result::Result<Self, Self::Error> {
@@ -42,6 +55,8 @@ impl TryFrom<&Row<'_>> for WindSqlRow {
             .expect("Parse b_info_maturitydate failed"),
             b_tendrst_referyield: value.get("b_tendrst_referyield")?,
             b_info_issueprice: value.get("b_info_issueprice")?,
+            // duckdb债券信息表暂无分期还本计划
+            b_info_amortization: None,
             // ..Default::default()
         })
     }
@@ -55,19 +70,30 @@ impl Bond {
             code.into()
         };
         let table = table_name.unwrap_or("bond_info");
-        if table
-            .chars()
-            .any(|c| !c.is_ascii_alphanumeric() && c != '_')
-        {
-            bail!("Invalid table name: {table}");
-        }
-        let sql = format!(
-            "select s_info_windcode, s_info_name, b_info_par, b_info_coupon, b_info_interesttype, b_info_couponrate, b_info_spread, b_info_interestfrequency, b_info_carrydate, b_info_maturitydate, b_tendrst_referyield, b_info_issueprice from {table} where s_info_windcode = ?"
-        );
+        check_table_name(table)?;
+        let sql = format!("select {BOND_INFO_COLUMNS} from {table} where s_info_windcode = ?");
         con.query_row(&sql, params![code], |row| {
             let row: WindSqlRow = row.try_into()?;
             Ok(row.try_into().unwrap())
         })
         .with_context(|| format!("Can not find bond {} in duckdb", code))
     }
+
+    /// 读取简称匹配`name_like`(SQL like语法, 如`%国债%`)的全部债券, 按债券代码排序
+    pub fn read_duckdb_all(
+        con: &Connection,
+        table_name: Option<&str>,
+        name_like: &str,
+    ) -> Result<Vec<Bond>> {
+        let table = table_name.unwrap_or("bond_info");
+        check_table_name(table)?;
+        let sql = format!(
+            "select {BOND_INFO_COLUMNS} from {table} where s_info_name like ? order by s_info_windcode"
+        );
+        let mut stmt = con.prepare(&sql)?;
+        let rows = stmt.query_map(params![name_like], |row| WindSqlRow::try_from(row))?;
+        rows.map(|row| row?.try_into())
+            .collect::<Result<Vec<Bond>>>()
+            .with_context(|| format!("Can not read bonds like {} from duckdb", name_like))
+    }
 }
@@ -0,0 +1,231 @@
+use crate::SmallStr;
+use crate::bond::{
+    Bond, BondDayCount, CouponRateStep, CouponType, InterestType, Market, PrincipalRepayment,
+    deserialize_date, serialize_date,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 版本0(未写入文件头)的债券字段布局
+///
+/// bincode不是自描述格式, Bond新增字段后旧的映射文件无法直接解码, 因此保留当时的字段顺序用于兼容读取
+#[derive(Serialize, Deserialize)]
+pub(super) struct BondV0 {
+    bond_code: SmallStr,
+    mkt: Market,
+    abbr: SmallStr,
+    par_value: f64,
+    cp_type: CouponType,
+    interest_type: InterestType,
+    cp_rate: f64,
+    base_rate: Option<f64>,
+    rate_spread: Option<f64>,
+    inst_freq: i32,
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    carry_date: NaiveDate,
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    maturity_date: NaiveDate,
+    day_count: BondDayCount,
+    issue_price: Option<f64>,
+}
+
+impl From<BondV0> for Bond {
+    fn from(b: BondV0) -> Self {
+        Bond {
+            bond_code: b.bond_code,
+            mkt: b.mkt,
+            abbr: b.abbr,
+            par_value: b.par_value,
+            cp_type: b.cp_type,
+            interest_type: b.interest_type,
+            cp_rate: b.cp_rate,
+            base_rate: b.base_rate,
+            rate_spread: b.rate_spread,
+            inst_freq: b.inst_freq,
+            carry_date: b.carry_date,
+            maturity_date: b.maturity_date,
+            day_count: b.day_count,
+            issue_price: b.issue_price,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<&Bond> for BondV0 {
+    fn from(b: &Bond) -> Self {
+        BondV0 {
+            bond_code: b.bond_code.clone(),
+            mkt: b.mkt,
+            abbr: b.abbr.clone(),
+            par_value: b.par_value,
+            cp_type: b.cp_type,
+            interest_type: b.interest_type,
+            cp_rate: b.cp_rate,
+            base_rate: b.base_rate,
+            rate_spread: b.rate_spread,
+            inst_freq: b.inst_freq,
+            carry_date: b.carry_date,
+            maturity_date: b.maturity_date,
+            day_count: b.day_count,
+            issue_price: b.issue_price,
+        }
+    }
+}
+
+/// 版本1的债券字段布局, 在版本0的基础上增加了票面利率调整计划
+#[derive(Serialize, Deserialize)]
+pub(super) struct BondV1 {
+    bond_code: SmallStr,
+    mkt: Market,
+    abbr: SmallStr,
+    par_value: f64,
+    cp_type: CouponType,
+    interest_type: InterestType,
+    cp_rate: f64,
+    base_rate: Option<f64>,
+    rate_spread: Option<f64>,
+    inst_freq: i32,
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    carry_date: NaiveDate,
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    maturity_date: NaiveDate,
+    day_count: BondDayCount,
+    issue_price: Option<f64>,
+    cp_rate_schedule: Vec<CouponRateStep>,
+}
+
+impl From<BondV1> for Bond {
+    fn from(b: BondV1) -> Self {
+        Bond {
+            bond_code: b.bond_code,
+            mkt: b.mkt,
+            abbr: b.abbr,
+            par_value: b.par_value,
+            cp_type: b.cp_type,
+            interest_type: b.interest_type,
+            cp_rate: b.cp_rate,
+            base_rate: b.base_rate,
+            rate_spread: b.rate_spread,
+            inst_freq: b.inst_freq,
+            carry_date: b.carry_date,
+            maturity_date: b.maturity_date,
+            day_count: b.day_count,
+            issue_price: b.issue_price,
+            cp_rate_schedule: b.cp_rate_schedule,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<&Bond> for BondV1 {
+    fn from(b: &Bond) -> Self {
+        BondV1 {
+            bond_code: b.bond_code.clone(),
+            mkt: b.mkt,
+            abbr: b.abbr.clone(),
+            par_value: b.par_value,
+            cp_type: b.cp_type,
+            interest_type: b.interest_type,
+            cp_rate: b.cp_rate,
+            base_rate: b.base_rate,
+            rate_spread: b.rate_spread,
+            inst_freq: b.inst_freq,
+            carry_date: b.carry_date,
+            maturity_date: b.maturity_date,
+            day_count: b.day_count,
+            issue_price: b.issue_price,
+            cp_rate_schedule: b.cp_rate_schedule.clone(),
+        }
+    }
+}
+
+/// 版本2的债券字段布局, 在版本1的基础上增加了分期还本计划
+#[derive(Serialize, Deserialize)]
+pub(super) struct BondV2 {
+    bond_code: SmallStr,
+    mkt: Market,
+    abbr: SmallStr,
+    par_value: f64,
+    cp_type: CouponType,
+    interest_type: InterestType,
+    cp_rate: f64,
+    base_rate: Option<f64>,
+    rate_spread: Option<f64>,
+    inst_freq: i32,
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    carry_date: NaiveDate,
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    maturity_date: NaiveDate,
+    day_count: BondDayCount,
+    issue_price: Option<f64>,
+    cp_rate_schedule: Vec<CouponRateStep>,
+    amortization: Vec<PrincipalRepayment>,
+}
+
+impl From<BondV2> for Bond {
+    fn from(b: BondV2) -> Self {
+        Bond {
+            bond_code: b.bond_code,
+            mkt: b.mkt,
+            abbr: b.abbr,
+            par_value: b.par_value,
+            cp_type: b.cp_type,
+            interest_type: b.interest_type,
+            cp_rate: b.cp_rate,
+            base_rate: b.base_rate,
+            rate_spread: b.rate_spread,
+            inst_freq: b.inst_freq,
+            carry_date: b.carry_date,
+            maturity_date: b.maturity_date,
+            day_count: b.day_count,
+            issue_price: b.issue_price,
+            cp_rate_schedule: b.cp_rate_schedule,
+            amortization: b.amortization,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<&Bond> for BondV2 {
+    fn from(b: &Bond) -> Self {
+        BondV2 {
+            bond_code: b.bond_code.clone(),
+            mkt: b.mkt,
+            abbr: b.abbr.clone(),
+            par_value: b.par_value,
+            cp_type: b.cp_type,
+            interest_type: b.interest_type,
+            cp_rate: b.cp_rate,
+            base_rate: b.base_rate,
+            rate_spread: b.rate_spread,
+            inst_freq: b.inst_freq,
+            carry_date: b.carry_date,
+            maturity_date: b.maturity_date,
+            day_count: b.day_count,
+            issue_price: b.issue_price,
+            cp_rate_schedule: b.cp_rate_schedule.clone(),
+            amortization: b.amortization.clone(),
+        }
+    }
+}
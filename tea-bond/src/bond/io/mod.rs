@@ -1,5 +1,6 @@
 #[cfg(feature = "duckdb")]
 mod duck;
+mod legacy;
 mod persist;
 mod wind_sql_row;
 
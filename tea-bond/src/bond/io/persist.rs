@@ -1,11 +1,12 @@
 use super::default_dir;
+use super::legacy::{BondV0, BondV1, BondV2};
 use crate::{SmallStr, bond::Bond};
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use parking_lot::Mutex;
 use std::{
     collections::HashMap,
     fs::File,
-    io::{BufReader, BufWriter, Write},
+    io::{BufWriter, Read, Write},
     path::{Path, PathBuf},
     sync::{Arc, LazyLock},
 };
@@ -31,12 +32,62 @@ fn normalize_code(code: &str) -> SmallStr {
     }
 }
 
-fn load_from_disk(path: &Path) -> Result<HashMap<SmallStr, Arc<Bond>>> {
-    let file = File::open(path).with_context(|| format!("Open bond map at {:?}", path))?;
-    let mut reader = BufReader::new(file);
+/// 映射文件头, 之后紧跟小端序的u32版本号
+const MAP_MAGIC: &[u8; 8] = b"TEABONDM";
+/// 当前映射文件版本, Bond字段布局变化时需要递增, 并在legacy中保留旧版本布局
+const MAP_VERSION: u32 = 3;
+
+fn decode_map(bytes: &[u8]) -> Result<BondMapType> {
     let cfg = bincode::config::standard();
-    bincode::serde::decode_from_std_read(&mut reader, cfg)
-        .context("Deserialize bond map with bincode")
+    let Some(rest) = bytes.strip_prefix(MAP_MAGIC.as_slice()) else {
+        // 未写入文件头的旧版本映射
+        let (map, _): (HashMap<SmallStr, BondV0>, _) =
+            bincode::serde::decode_from_slice(bytes, cfg)
+                .context("Deserialize legacy bond map with bincode")?;
+        return Ok(map
+            .into_iter()
+            .map(|(k, v)| (k, Arc::new(v.into())))
+            .collect());
+    };
+    let (version, payload) = rest
+        .split_first_chunk::<4>()
+        .context("Bond map header is truncated")?;
+    match u32::from_le_bytes(*version) {
+        1 => {
+            // 版本1尚未包含分期还本计划
+            let (map, _): (HashMap<SmallStr, BondV1>, _) =
+                bincode::serde::decode_from_slice(payload, cfg)
+                    .context("Deserialize bond map of version 1 with bincode")?;
+            Ok(map
+                .into_iter()
+                .map(|(k, v)| (k, Arc::new(v.into())))
+                .collect())
+        }
+        2 => {
+            // 版本2尚未包含行权条款
+            let (map, _): (HashMap<SmallStr, BondV2>, _) =
+                bincode::serde::decode_from_slice(payload, cfg)
+                    .context("Deserialize bond map of version 2 with bincode")?;
+            Ok(map
+                .into_iter()
+                .map(|(k, v)| (k, Arc::new(v.into())))
+                .collect())
+        }
+        MAP_VERSION => {
+            let (map, _) = bincode::serde::decode_from_slice(payload, cfg)
+                .context("Deserialize bond map with bincode")?;
+            Ok(map)
+        }
+        v => bail!("Unsupported bond map version: {}", v),
+    }
+}
+
+fn load_from_disk(path: &Path) -> Result<HashMap<SmallStr, Arc<Bond>>> {
+    let mut file = File::open(path).with_context(|| format!("Open bond map at {:?}", path))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .with_context(|| format!("Read bond map at {:?}", path))?;
+    decode_map(&bytes)
 }
 
 fn flush_to_disk(path: &Path, map: &HashMap<SmallStr, Arc<Bond>>) -> Result<()> {
@@ -46,6 +97,12 @@ fn flush_to_disk(path: &Path, map: &HashMap<SmallStr, Arc<Bond>>) -> Result<()>
     }
     let file = File::create(path).with_context(|| format!("Create bond map at {:?}", path))?;
     let mut writer = BufWriter::new(file);
+    writer
+        .write_all(MAP_MAGIC)
+        .context("Write bond map header")?;
+    writer
+        .write_all(&MAP_VERSION.to_le_bytes())
+        .context("Write bond map header")?;
     let cfg = bincode::config::standard();
     bincode::serde::encode_into_std_write(map, &mut writer, cfg).context("Serialize bond map")?;
     writer.flush().context("Flush bond map writer")
@@ -88,6 +145,16 @@ impl Bond {
             .with_context(|| format!("Bond {} not found in disk map", normalized))
     }
 
+    /// 读取内存/磁盘映射中的全部债券, 按债券代码排序；首次调用会尝试从磁盘加载。
+    pub fn read_disk_all() -> Vec<Arc<Self>> {
+        ensure_loaded();
+        let guard = BOND_MAP.lock();
+        let map = guard.as_ref().expect("bond map should be initialized");
+        let mut bonds: Vec<_> = map.values().cloned().collect();
+        bonds.sort_by(|a, b| a.bond_code.cmp(&b.bond_code));
+        bonds
+    }
+
     /// 写入/更新内存映射；`flush_all` 为 true 时同步落盘。
     pub fn save_disk(&self, flush_all: bool) -> Result<()> {
         ensure_loaded();
@@ -101,3 +168,86 @@ impl Bond {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bond::{CouponRateStep, EmbeddedOption, PrincipalRepayment};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_bond_map_versions() {
+        let bond = Bond {
+            bond_code: "240012.IB".into(),
+            cp_rate: 0.0167,
+            carry_date: NaiveDate::from_ymd_opt(2024, 6, 15).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2026, 6, 15).unwrap(),
+            ..Default::default()
+        };
+        let cfg = bincode::config::standard();
+        // 旧版本映射文件没有文件头
+        let legacy: HashMap<SmallStr, BondV0> =
+            HashMap::from([(bond.bond_code.clone(), BondV0::from(&bond))]);
+        let bytes = bincode::serde::encode_to_vec(&legacy, cfg).unwrap();
+        let map = decode_map(&bytes).unwrap();
+        let decoded = &map[bond.bond_code()];
+        assert_eq!(decoded.cp_rate, 0.0167);
+        assert_eq!(decoded.maturity_date, bond.maturity_date);
+        assert!(decoded.cp_rate_schedule.is_empty());
+
+        let bond = Bond {
+            cp_rate_schedule: vec![CouponRateStep::new(bond.carry_date, 0.02)],
+            ..bond
+        };
+        // 版本1的映射文件
+        let v1: HashMap<SmallStr, BondV1> =
+            HashMap::from([(bond.bond_code.clone(), BondV1::from(&bond))]);
+        let mut bytes = MAP_MAGIC.to_vec();
+        bytes.extend(1u32.to_le_bytes());
+        bytes.extend(bincode::serde::encode_to_vec(&v1, cfg).unwrap());
+        let map = decode_map(&bytes).unwrap();
+        assert_eq!(
+            map[bond.bond_code()].cp_rate_schedule,
+            bond.cp_rate_schedule
+        );
+        assert!(map[bond.bond_code()].amortization.is_empty());
+
+        let bond = Bond {
+            amortization: vec![PrincipalRepayment::new(
+                NaiveDate::from_ymd_opt(2025, 6, 15).unwrap(),
+                0.5,
+            )],
+            ..bond
+        };
+        // 版本2的映射文件
+        let v2: HashMap<SmallStr, BondV2> =
+            HashMap::from([(bond.bond_code.clone(), BondV2::from(&bond))]);
+        let mut bytes = MAP_MAGIC.to_vec();
+        bytes.extend(2u32.to_le_bytes());
+        bytes.extend(bincode::serde::encode_to_vec(&v2, cfg).unwrap());
+        let map = decode_map(&bytes).unwrap();
+        assert_eq!(map[bond.bond_code()].amortization, bond.amortization);
+        assert!(!map[bond.bond_code()].has_embedded_option());
+
+        let bond = Bond {
+            embedded_options: vec![EmbeddedOption::put(
+                bond.carry_date + chrono::Months::new(12),
+            )],
+            ..bond
+        };
+        let path = std::env::temp_dir().join(format!("tea_bond_map_{}.map", std::process::id()));
+        let map = HashMap::from([(bond.bond_code.clone(), Arc::new(bond.clone()))]);
+        flush_to_disk(&path, &map).unwrap();
+        let decoded = load_from_disk(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            decoded[bond.bond_code()].cp_rate_schedule,
+            bond.cp_rate_schedule
+        );
+        assert_eq!(decoded[bond.bond_code()].amortization, bond.amortization);
+        assert_eq!(
+            decoded[bond.bond_code()].embedded_options,
+            bond.embedded_options
+        );
+    }
+}
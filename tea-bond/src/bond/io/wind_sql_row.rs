@@ -1,8 +1,8 @@
 use crate::SmallStr;
-use crate::bond::{Bond, BondDayCount, CouponType, InterestType, Market};
+use crate::bond::{Bond, BondDayCount, CouponType, InterestType, Market, PrincipalRepayment};
 use chrono::NaiveDate;
 // use serde::{Deserialize, Serialize};
-use anyhow::{Result, bail};
+use anyhow::{Context, Result, bail};
 // use crate::bond::impl_traits::{deserialize_date, serialize_date};
 
 #[derive(Debug, Clone)]
@@ -19,6 +19,8 @@ pub struct WindSqlRow {
     pub b_info_maturitydate: NaiveDate,
     pub b_tendrst_referyield: Option<f64>,
     pub b_info_issueprice: Option<f64>,
+    /// 分期还本计划, 格式为`还本日:偿还比例(%)`, 多项以逗号分隔, 如`20260410:20,20270410:20`
+    pub b_info_amortization: Option<SmallStr>,
 }
 
 impl Default for WindSqlRow {
@@ -36,6 +38,7 @@ impl Default for WindSqlRow {
             b_info_maturitydate: NaiveDate::default(),
             b_tendrst_referyield: None,
             b_info_issueprice: None,
+            b_info_amortization: None,
         }
     }
 }
@@ -95,6 +98,29 @@ fn get_inst_freq(coupon_type: &CouponType, freq: Option<&str>) -> i32 {
     }
 }
 
+fn get_amortization(schedule: Option<&str>) -> Result<Vec<PrincipalRepayment>> {
+    let Some(schedule) = schedule else {
+        return Ok(Vec::new());
+    };
+    schedule
+        .split([',', ';'])
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+            let (date, ratio) = item
+                .split_once(':')
+                .with_context(|| format!("Invalid amortization item: {item}"))?;
+            let date = NaiveDate::parse_from_str(date.trim(), "%Y%m%d")
+                .with_context(|| format!("Invalid amortization date: {date}"))?;
+            let ratio: f64 = ratio
+                .trim()
+                .parse()
+                .with_context(|| format!("Invalid amortization ratio: {ratio}"))?;
+            Ok(PrincipalRepayment::new(date, round(ratio * 0.01, 8)))
+        })
+        .collect()
+}
+
 impl TryFrom<WindSqlRow> for Bond {
     type Error = anyhow::Error;
     fn try_from(row: WindSqlRow) -> Result<Self> {
@@ -120,6 +146,41 @@ impl TryFrom<WindSqlRow> for Bond {
             maturity_date: row.b_info_maturitydate,
             issue_price: row.b_info_issueprice,
             day_count: BondDayCount::default(),
+            cp_rate_schedule: Vec::new(),
+            amortization: get_amortization(row.b_info_amortization.as_deref())?,
+            embedded_options: Vec::new(),
+            schedule_cache: Default::default(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amortization_from_wind() {
+        let row = WindSqlRow {
+            s_info_windcode: "2080001.IB".into(),
+            b_info_couponrate: Some(3.5),
+            b_info_carrydate: NaiveDate::from_ymd_opt(2020, 4, 10).unwrap(),
+            b_info_maturitydate: NaiveDate::from_ymd_opt(2027, 4, 10).unwrap(),
+            b_info_amortization: Some(
+                "20230410:20, 20240410:20,20250410:20;20260410:20,20270410:20".into(),
+            ),
+            ..Default::default()
+        };
+        let bond: Bond = row.clone().try_into().unwrap();
+        assert!(bond.is_amortizing());
+        assert_eq!(bond.amortization.len(), 5);
+        assert_eq!(bond.amortization[0].ratio, 0.2);
+        let date = NaiveDate::from_ymd_opt(2025, 4, 10).unwrap();
+        assert!((bond.remaining_par(date) - 40.).abs() < 1e-10);
+
+        let row = WindSqlRow {
+            b_info_amortization: Some("20230410".into()),
+            ..row
+        };
+        assert!(Bond::try_from(row).is_err());
+    }
+}
@@ -1,22 +1,38 @@
 mod bond_ytm;
 mod cached_bond;
+mod cash_flow;
 #[cfg(feature = "download")]
 mod download;
+mod embedded_option;
 mod enums;
+mod floating;
+mod forward;
 mod impl_convert;
 mod impl_traits;
 mod io;
+mod risk;
+mod schedule;
+mod spread;
+mod zero_coupon;
 
 pub use bond_ytm::BondYtm;
 pub use cached_bond::CachedBond;
-pub use enums::{BondDayCount, CouponType, InterestType, Market};
+use cash_flow::ScheduleCache;
+pub use cash_flow::{CashFlow, CashFlowSchedule};
+pub use embedded_option::EmbeddedOption;
+pub use enums::{BondDayCount, CouponType, InterestType, Market, OptionType};
+pub use floating::RateFixings;
+pub(crate) use io::default_dir;
 pub use io::{WindSqlRow, free_bond_map};
+pub use schedule::{CouponRateStep, PrincipalRepayment};
 
 use crate::SmallStr;
+use crate::YieldSolver;
 use crate::day_counter::{ACTUAL, DayCountRule};
-use anyhow::{Result, bail, ensure};
+use crate::utils::month_delta;
+use anyhow::{Context, Result, bail, ensure};
 use chrono::{Datelike, Months, NaiveDate};
-use impl_traits::{deserialize_date, serialize_date};
+pub(crate) use impl_traits::{deserialize_date, serialize_date};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -57,6 +73,21 @@ pub struct Bond {
     pub day_count: BondDayCount, // 计息基准, 如A/365F
     #[serde(default)]
     pub issue_price: Option<f64>, // 发行价
+    #[serde(default)]
+    pub cp_rate_schedule: Vec<CouponRateStep>, // 票面利率调整计划, 累进利率债券适用, 为空时各期均按cp_rate计息
+    #[serde(default)]
+    pub amortization: Vec<PrincipalRepayment>, // 分期还本计划, 为空时到期一次偿还本金
+    #[serde(default)]
+    pub embedded_options: Vec<EmbeddedOption>, // 赎回/回售等行权条款, 含权债券适用
+    #[serde(skip)]
+    pub(crate) schedule_cache: ScheduleCache, // 现金流计划缓存
+}
+
+/// 定价用的剩余现金流
+struct RemainCashFlows {
+    flows: Vec<(CashFlow, f64)>, // 现金流及其距下一付息日的整付息周期数
+    remain_days: f64,            // 距下一付息日的计息天数
+    period_days: f64,            // 当前付息周期的计息天数
 }
 
 const fn default_par_value() -> f64 {
@@ -117,6 +148,20 @@ impl Bond {
         self.cp_type == CouponType::OneTime
     }
 
+    /// 是否为记账式附息国债(含特别国债), 即国债期货可交割券的债券品种
+    ///
+    /// 按债券简称识别, 剔除贴现国债、储蓄国债及含权、浮息、分期还本等非标准国债
+    pub fn is_treasury(&self) -> bool {
+        self.abbr.contains("国债")
+            && !self.abbr.contains("贴现")
+            && !self.abbr.contains("储蓄")
+            && self.cp_type == CouponType::CouponBear
+            && self.interest_type == InterestType::Fixed
+            && self.cp_rate_schedule.is_empty()
+            && self.amortization.is_empty()
+            && !self.has_embedded_option()
+    }
+
     #[inline]
     pub fn remain_year(&self, date: NaiveDate) -> f64 {
         let year_diff = self.maturity_date.year() - date.year();
@@ -171,6 +216,24 @@ impl Bond {
         Ok(day_counts)
     }
 
+    /// 最后一个付息周期单利折现所用的年度天数
+    ///
+    /// ACT/ACT按最后一个计息年度的实际天数, 其余按计息基准的年度天数
+    fn last_period_year_days(&self) -> Result<f64> {
+        match self.day_count.year_days() {
+            Some(year_days) => Ok(year_days),
+            None => Ok(self.get_last_cp_year_days()? as f64),
+        }
+    }
+
+    /// 按计息基准计算的年化期限
+    ///
+    /// ACT/ACT按实际天数/365计算
+    #[inline]
+    pub fn year_fraction(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.day_count.count_days(start, end) as f64 / self.day_count.year_days().unwrap_or(365.)
+    }
+
     #[inline]
     /// 确保日期在有效范围内
     fn ensure_date_valid(&self, date: NaiveDate) -> Result<NaiveDate> {
@@ -200,18 +263,29 @@ impl Bond {
             bail!("Zero Coupon bond does not have coupon dates");
         }
         let date = self.ensure_date_valid(date)?;
-        let date_offset = self.get_cp_offset()?;
-        let mut cp_date = self.carry_date;
-        let mut cp_date_next = cp_date + date_offset;
-        // 最多一年付息两次，目前超长期国债为50年，理论上不会超过200次循环
-        for _ in 0..220 {
-            if date >= cp_date && date < cp_date_next {
-                return Ok((cp_date, cp_date_next));
-            }
-            cp_date = cp_date_next;
-            cp_date_next = cp_date + date_offset;
+        let offset = self.get_cp_offset()?;
+        ensure!(offset.as_u32() > 0, "Invalid inst_freq: {}", self.inst_freq);
+        // 付息日以起息日为锚点推算, 先按月份差估计所在期数再微调
+        let mut n = month_delta(self.carry_date, date).max(0) as u32 / offset.as_u32();
+        while n > 0 && self.nth_cp_date(n, offset) > date {
+            n -= 1;
+        }
+        while self.nth_cp_date(n + 1, offset) <= date {
+            n += 1;
+        }
+        Ok((self.nth_cp_date(n, offset), self.nth_cp_date(n + 1, offset)))
+    }
+
+    #[inline]
+    fn next_cp_date_or_nearest(
+        &self,
+        date: NaiveDate,
+        next_cp_date: Option<NaiveDate>,
+    ) -> Result<NaiveDate> {
+        match next_cp_date {
+            Some(d) => Ok(d),
+            None => Ok(self.get_nearest_cp_date(date)?.1),
         }
-        bail!("Failed to find nearest coupon date");
     }
 
     /// 剩余的付息次数
@@ -221,17 +295,9 @@ impl Bond {
         } else if self.is_one_time() {
             return Ok(1);
         }
-        use tea_calendar::Calendar;
-        let mut next_cp_date =
-            next_cp_date.unwrap_or_else(|| self.get_nearest_cp_date(date).unwrap().1);
-        let mut cp_num = 0;
-        let offset = self.get_cp_offset()?;
-        let maturity_date = self.mkt.find_workday(self.maturity_date, 0);
-        while next_cp_date <= maturity_date {
-            cp_num += 1;
-            next_cp_date = next_cp_date + offset;
-        }
-        Ok(cp_num)
+        let next_cp_date = self.next_cp_date_or_nearest(date, next_cp_date)?;
+        let schedule = self.cash_flow_schedule()?;
+        Ok(schedule.between(next_cp_date, self.maturity_date).len() as i32)
     }
 
     /// 剩余的付息次数
@@ -241,24 +307,9 @@ impl Bond {
         until_date: NaiveDate,
         next_cp_date: Option<NaiveDate>,
     ) -> Result<i32> {
-        if self.is_zero_coupon() {
-            return Ok(0);
-        }
-        let mut next_cp_date =
-            next_cp_date.unwrap_or_else(|| self.get_nearest_cp_date(date).unwrap().1);
-        if next_cp_date > until_date {
-            // 对于正好相等的情况，由于应计利息会被重置为0，因此剩余付息次数不应该返回0
-            // 否则计算的持有期收益将会不连续
-            // 此处与原python代码处理不同，当期货缴款日正好是付息日时，按1处理
-            return Ok(0);
-        }
-        let mut cp_num = 0;
-        let offset = self.get_cp_offset()?;
-        while next_cp_date <= until_date {
-            cp_num += 1;
-            next_cp_date = next_cp_date + offset;
-        }
-        Ok(cp_num)
+        Ok(self
+            .remain_cp_dates_until(date, until_date, next_cp_date)?
+            .len() as i32)
     }
 
     /// 获得剩余的付息日期列表
@@ -271,19 +322,53 @@ impl Bond {
         if self.is_zero_coupon() {
             return Ok(vec![]);
         }
-        let mut next_cp_date =
-            next_cp_date.unwrap_or_else(|| self.get_nearest_cp_date(date).unwrap().1);
-        if next_cp_date > until_date {
-            return Ok(vec![]);
-        }
-        let mut cp_dates = vec![];
-        let offset = self.get_cp_offset()?;
-        while next_cp_date <= until_date {
-            cp_dates.push(next_cp_date);
-            next_cp_date = next_cp_date + offset;
-        }
-        Ok(cp_dates)
+        let next_cp_date = self.next_cp_date_or_nearest(date, next_cp_date)?;
+        // 对于付息日正好等于截止日的情况，由于应计利息会被重置为0，因此应计入该次付息
+        // 否则计算的持有期收益将会不连续
+        // 此处与原python代码处理不同，当期货缴款日正好是付息日时，按1处理
+        let schedule = self.cash_flow_schedule()?;
+        Ok(schedule
+            .between(next_cp_date, until_date)
+            .iter()
+            .map(|cf| cf.nominal_date)
+            .collect())
     }
+
+    /// 计算日之后用于定价的剩余现金流
+    fn remain_cash_flows(
+        &self,
+        date: NaiveDate,
+        cp_dates: Option<(NaiveDate, NaiveDate)>,
+        remain_cp_num: Option<i32>,
+    ) -> Result<RemainCashFlows> {
+        let (pre_cp_date, next_cp_date) = if let Some(cp_dates) = cp_dates {
+            cp_dates
+        } else {
+            self.get_nearest_cp_date(date)?
+        };
+        let offset = self.get_cp_offset()?.as_u32() as i32;
+        ensure!(offset > 0, "Invalid inst_freq: {}", self.inst_freq);
+        let schedule = self.cash_flow_schedule()?;
+        let flows = schedule.between(next_cp_date, self.maturity_date);
+        let n = remain_cp_num.map_or(flows.len(), |n| (n.max(0) as usize).min(flows.len()));
+        let flows = flows[..n]
+            .iter()
+            .map(|cf| {
+                let period = month_delta(next_cp_date, cf.nominal_date) / offset;
+                (*cf, period as f64)
+            })
+            .collect();
+        let period_days = match self.day_count.year_days() {
+            Some(year_days) => year_days / self.inst_freq as f64,
+            None => ACTUAL.count_days(pre_cp_date, next_cp_date) as f64,
+        };
+        Ok(RemainCashFlows {
+            flows,
+            remain_days: self.day_count.count_days(date, next_cp_date) as f64,
+            period_days,
+        })
+    }
+
     /// 计算应计利息
     ///
     /// 银行间和交易所的计算规则不同,银行间是算头不算尾,而交易所是算头又算尾
@@ -292,21 +377,15 @@ impl Bond {
         calculating_date: NaiveDate,
         cp_dates: Option<(NaiveDate, NaiveDate)>, // 前后付息日，如果已经计算完成可以直接传入避免重复计算
     ) -> Result<f64> {
+        self.ensure_fixed_rate()?;
         match self.cp_type {
-            CouponType::ZeroCoupon => {
-                // 贴现债券
-                if let Some(issue_price) = self.issue_price {
-                    // TODO: 交易所债券的计算规则有所不同, 可参考Wind计算说明进行实现
-                    Ok((self.par_value - issue_price)
-                        * ACTUAL.count_days(self.carry_date, calculating_date) as f64
-                        / ACTUAL.count_days(self.carry_date, self.maturity_date) as f64)
-                } else {
-                    // 近似算法
-                    let days = ACTUAL.count_days(self.carry_date, calculating_date);
-                    Ok(self.cp_rate * self.par_value * days as f64 / 365.)
-                }
-            }
+            // 贴现债券
+            CouponType::ZeroCoupon => Ok(self.zero_coupon_accrued_interest(calculating_date)),
             CouponType::OneTime => {
+                if let Some(year_days) = self.day_count.year_days() {
+                    let days = self.day_count.count_days(self.carry_date, calculating_date);
+                    return Ok(self.cp_rate * self.par_value * days as f64 / year_days);
+                }
                 let year = ((calculating_date - self.carry_date).num_days() as f64 / 365.)
                     .floor()
                     .max(0.);
@@ -325,11 +404,23 @@ impl Bond {
                 } else {
                     self.get_nearest_cp_date(calculating_date)?
                 };
+                // 当期适用的票面利率, 累进利率债券各期不同
+                let cp_rate = self.cp_rate_at(pre_cp_date);
+                // 按期初剩余本金计息, 分期还本债券折算为每百元剩余本金的应计利息
+                let par_value =
+                    self.remaining_par(pre_cp_date) / self.remaining_par_factor(calculating_date);
+                if let Some(year_days) = self.day_count.year_days() {
+                    // 非ACT/ACT计息基准按计息天数/年度天数计算, 交易所仍算头又算尾
+                    let extra_day = if self.mkt == Market::IB { 0 } else { 1 };
+                    let inst_accrued_days =
+                        self.day_count.count_days(pre_cp_date, calculating_date) + extra_day;
+                    return Ok(cp_rate * par_value * inst_accrued_days as f64 / year_days);
+                }
                 match self.mkt {
                     Market::IB => {
                         // 银行间是算头不算尾，计算实际天数（自然日）
                         let inst_accrued_days = ACTUAL.count_days(pre_cp_date, calculating_date);
-                        let coupon = self.cp_rate * self.par_value / self.inst_freq as f64;
+                        let coupon = cp_rate * par_value / self.inst_freq as f64;
                         // 当前付息周期实际天数
                         let present_cp_period_days = ACTUAL.count_days(pre_cp_date, next_cp_date);
                         Ok(coupon * inst_accrued_days as f64 / present_cp_period_days as f64)
@@ -338,7 +429,7 @@ impl Bond {
                         // 交易所是算头又算尾
                         let inst_accrued_days =
                             1 + ACTUAL.count_days(pre_cp_date, calculating_date);
-                        Ok(self.cp_rate * self.par_value * inst_accrued_days as f64 / 365.0)
+                        Ok(cp_rate * par_value * inst_accrued_days as f64 / 365.0)
                     }
                 }
             }
@@ -353,36 +444,45 @@ impl Bond {
         cp_dates: Option<(NaiveDate, NaiveDate)>,
         remain_cp_num: Option<i32>,
     ) -> Result<f64> {
+        let ytm = self.check_ytm(ytm);
         if self.is_zero_coupon() {
-            let remain_year = self.remain_year(date);
-            assert!(remain_year < 1.);
-            return Ok(self.par_value / (1.0 + ytm * remain_year));
+            return self.zero_coupon_dirty_price(ytm, date);
         }
-        let ytm = self.check_ytm(ytm);
+        let remain = self.remain_cash_flows(date, cp_dates, remain_cp_num)?;
+        self.dirty_price_with_remain_flows(ytm, date, remain)
+    }
+
+    /// 通过ytm对剩余现金流折现得到全价
+    fn dirty_price_with_remain_flows(
+        &self,
+        ytm: f64,
+        date: NaiveDate,
+        remain: RemainCashFlows,
+    ) -> Result<f64> {
         let inst_freq = self.inst_freq as f64;
-        let coupon = self.get_coupon();
-        let (pre_cp_date, next_cp_date) = if let Some(cp_dates) = cp_dates {
-            cp_dates
-        } else {
-            self.get_nearest_cp_date(date)?
+        let RemainCashFlows {
+            flows,
+            remain_days,
+            period_days: ty,
+        } = remain;
+        let Some((_, last_period)) = flows.last() else {
+            // 已无剩余现金流
+            return Ok(0.);
         };
-        let remain_days = ACTUAL.count_days(date, next_cp_date) as f64;
-        let n = remain_cp_num.unwrap_or_else(|| self.remain_cp_num(date, None).unwrap());
-        // TODO: take day_count into account
-        if n <= 1 {
-            let ty = self.get_last_cp_year_days()? as f64;
-            let forward_value = self.par_value + coupon;
+        let price = if *last_period == 0. {
+            // 处于最后一个付息周期
+            let ty = self.last_period_year_days()?;
+            let forward_value = flows.iter().map(|(cf, _)| cf.amount()).sum::<f64>();
             let discount_factor = 1.0 + ytm * remain_days / ty;
-            Ok(forward_value / discount_factor)
+            forward_value / discount_factor
         } else {
-            let ty = ACTUAL.count_days(pre_cp_date, next_cp_date) as f64;
-            let coupon_cf = (0..n).fold(0., |acc, i| {
-                let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + i as f64);
-                acc + coupon / discount_factor
-            });
-            let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + (n - 1) as f64);
-            Ok(self.par_value / discount_factor + coupon_cf)
-        }
+            flows.iter().fold(0., |acc, (cf, period)| {
+                let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + period);
+                acc + cf.amount() / discount_factor
+            })
+        };
+        // 分期还本债券按每百元剩余本金报价
+        Ok(price / self.remaining_par_factor(date))
     }
 
     /// 通过ytm计算债券净价
@@ -405,44 +505,82 @@ impl Bond {
     }
 
     /// 通过债券全价计算ytm
+    #[inline]
     pub fn calc_ytm_with_price(
         &self,
         dirty_price: f64,
         date: NaiveDate,
         cp_dates: Option<(NaiveDate, NaiveDate)>,
         remain_cp_num: Option<i32>,
+    ) -> Result<f64> {
+        self.calc_ytm_with_solver(
+            dirty_price,
+            date,
+            cp_dates,
+            remain_cp_num,
+            &YieldSolver::default(),
+        )
+    }
+
+    /// 通过债券全价计算ytm, 可指定求解区间、精度及最大迭代次数
+    ///
+    /// 求解区间内无解时返回错误
+    pub fn calc_ytm_with_solver(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        cp_dates: Option<(NaiveDate, NaiveDate)>,
+        remain_cp_num: Option<i32>,
+        solver: &YieldSolver,
     ) -> Result<f64> {
         if self.is_zero_coupon() {
-            let ty = ACTUAL.count_days(self.carry_date, self.carry_date + chrono::Months::new(12))
-                as f64;
-            return Ok((self.par_value / dirty_price - 1.) * ty
-                / ACTUAL.count_days(date, self.maturity_date) as f64);
+            return self.zero_coupon_ytm(dirty_price, date);
         }
+        let remain = self.remain_cash_flows(date, cp_dates, remain_cp_num)?;
+        self.ytm_with_remain_flows(dirty_price, date, remain, solver)
+    }
+
+    /// 通过全价求解使剩余现金流现值与之相等的收益率
+    fn ytm_with_remain_flows(
+        &self,
+        dirty_price: f64,
+        date: NaiveDate,
+        remain: RemainCashFlows,
+        solver: &YieldSolver,
+    ) -> Result<f64> {
         let inst_freq = self.inst_freq as f64;
-        let coupon = self.get_coupon();
-        let (pre_cp_date, next_cp_date) =
-            cp_dates.unwrap_or_else(|| self.get_nearest_cp_date(date).unwrap());
-        let remain_days = ACTUAL.count_days(date, next_cp_date) as f64;
-
-        let n = remain_cp_num.unwrap_or_else(|| self.remain_cp_num(date, None).unwrap());
-        if n > 1 {
-            let ty = ACTUAL.count_days(pre_cp_date, next_cp_date) as f64;
-            // 不在最后一个付息周期内
-            use crate::utils::bisection_find_ytm;
+        let RemainCashFlows {
+            flows,
+            remain_days,
+            period_days: ty,
+        } = remain;
+        let Some((_, last_period)) = flows.last() else {
+            return Ok(f64::NAN);
+        };
+        // 报价为每百元剩余本金的全价, 折算为按发行面值计的现金流现值
+        let dirty_price = dirty_price * self.remaining_par_factor(date);
+        if *last_period > 0. {
+            // 不在最后一个付息周期内, 现值对收益率的导数可解析求得
             let f = |ytm: f64| {
-                let coupon_cf = (0..n).fold(0., |acc, i| {
-                    let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + i as f64);
-                    acc + coupon / discount_factor
-                });
-                let discount_factor =
-                    (1. + ytm / inst_freq).powf(remain_days / ty + (n - 1) as f64);
-                self.par_value / discount_factor + coupon_cf - dirty_price
+                flows.iter().fold(0., |acc, (cf, period)| {
+                    let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + period);
+                    acc + cf.amount() / discount_factor
+                }) - dirty_price
+            };
+            let df = |ytm: f64| {
+                flows.iter().fold(0., |acc, (cf, period)| {
+                    let n = remain_days / ty + period;
+                    let discount_factor = (1. + ytm / inst_freq).powf(n + 1.);
+                    acc - n / inst_freq * cf.amount() / discount_factor
+                })
             };
-            Ok(bisection_find_ytm(f, 1e-4, 0.3, Some(12)))
+            solver
+                .solve(f, df, self.cp_rate)
+                .with_context(|| format!("Failed to solve ytm of bond {}", self.code()))
         } else {
-            let ty = self.get_last_cp_year_days()? as f64;
+            let ty = self.last_period_year_days()?;
             // 只剩最后一次付息
-            let forward_value = self.par_value + coupon;
+            let forward_value = flows.iter().map(|(cf, _)| cf.amount()).sum::<f64>();
             Ok((forward_value - dirty_price) / dirty_price / (remain_days / ty))
         }
     }
@@ -455,29 +593,36 @@ impl Bond {
         cp_dates: Option<(NaiveDate, NaiveDate)>,
         remain_cp_num: Option<i32>,
     ) -> Result<f64> {
+        if self.is_zero_coupon() {
+            return self.zero_coupon_macaulay_duration(date);
+        }
         let ytm = self.check_ytm(ytm);
+        let remain = self.remain_cash_flows(date, cp_dates, remain_cp_num)?;
+        Ok(self.macaulay_duration_with_remain_flows(ytm, remain))
+    }
+
+    /// 剩余现金流的麦考利久期
+    fn macaulay_duration_with_remain_flows(&self, ytm: f64, remain: RemainCashFlows) -> f64 {
         let inst_freq = self.inst_freq as f64;
-        let coupon = self.get_coupon();
-        let (pre_cp_date, next_cp_date) =
-            cp_dates.unwrap_or_else(|| self.get_nearest_cp_date(date).unwrap());
-        let remain_days = ACTUAL.count_days(date, next_cp_date) as f64;
-        let ty = ACTUAL.count_days(pre_cp_date, next_cp_date) as f64;
-        let n = remain_cp_num.unwrap_or_else(|| self.remain_cp_num(date, None).unwrap());
-        let cashflow = (0..n)
-            .map(|i| {
-                let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + i as f64);
-                let cashflow = coupon / discount_factor;
-                let time = remain_days / 365. + i as f64 / inst_freq;
-                (cashflow, time)
+        let RemainCashFlows {
+            flows,
+            remain_days,
+            period_days: ty,
+        } = remain;
+        if flows.is_empty() {
+            return 0.;
+        }
+        let year_days = self.day_count.year_days().unwrap_or(365.);
+        let cashflow = flows
+            .iter()
+            .map(|(cf, period)| {
+                let discount_factor = (1. + ytm / inst_freq).powf(remain_days / ty + period);
+                let time = remain_days / year_days + period / inst_freq;
+                (cf.amount() / discount_factor, time)
             })
-            .chain(std::iter::once((
-                self.par_value / (1. + ytm / inst_freq).powf(remain_days / ty + (n - 1) as f64),
-                remain_days / 365. + (n - 1) as f64 / inst_freq,
-            )))
             .collect::<Vec<_>>();
         let p = cashflow.iter().map(|(cf, _t)| cf).sum::<f64>();
-        let duration = cashflow.iter().map(|(cf, t)| cf * t).sum::<f64>() / p;
-        Ok(duration)
+        cashflow.iter().map(|(cf, t)| cf * t).sum::<f64>() / p
     }
 
     #[inline]
@@ -489,10 +634,10 @@ impl Bond {
         cp_dates: Option<(NaiveDate, NaiveDate)>,
         remain_cp_num: Option<i32>,
     ) -> Result<f64> {
+        let ytm = self.check_ytm(ytm);
         if self.is_zero_coupon() {
-            return Ok(self.remain_year(date));
+            return self.zero_coupon_duration(ytm, date);
         }
-        let ytm = self.check_ytm(ytm);
         let duration = self.calc_macaulay_duration(ytm, date, cp_dates, remain_cp_num)?;
         Ok(duration / (1. + ytm / self.inst_freq as f64))
     }
@@ -621,4 +766,74 @@ mod tests {
         let last_cp_year_days = bond.get_last_cp_year_days().unwrap();
         assert_eq!(last_cp_year_days, 365);
     }
+
+    #[test]
+    fn test_day_count_pricing() {
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.04,
+            inst_freq: 2,
+            carry_date: NaiveDate::from_ymd_opt(2020, 3, 10).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2030, 3, 10).unwrap(),
+            day_count: BondDayCount::Thirty360,
+            ..Default::default()
+        };
+        // 30/360下每个付息周期均为180天
+        let date = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+        let ai = bond.calc_accrued_interest(date, None).unwrap();
+        assert!((ai - 4. * 30. / 360.).abs() < 1e-12);
+        // 付息日按票面利率定价应等于面值
+        let date = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap();
+        let price = bond
+            .calc_dirty_price_with_ytm(0.04, date, None, None)
+            .unwrap();
+        assert!((price - 100.).abs() < 1e-10);
+        let date = NaiveDate::from_ymd_opt(2024, 7, 25).unwrap();
+        let price = bond
+            .calc_dirty_price_with_ytm(0.035, date, None, None)
+            .unwrap();
+        let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+        assert!((ytm - 0.035).abs() < 1e-8);
+
+        // 交易所A/365F按算头又算尾计息
+        let bond = Bond {
+            mkt: Market::SSE,
+            day_count: BondDayCount::Act365F,
+            ..bond
+        };
+        let date = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+        let ai = bond.calc_accrued_interest(date, None).unwrap();
+        assert!((ai - 4. * 32. / 365.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ytm_solver() {
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.05,
+            inst_freq: 2,
+            carry_date: NaiveDate::from_ymd_opt(2022, 9, 15).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2029, 9, 15).unwrap(),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 1, 20).unwrap();
+        // 负收益率与高收益率均可还原
+        for ytm in [-0.003, 0.0185, 0.45] {
+            let price = bond
+                .calc_dirty_price_with_ytm(ytm, date, None, None)
+                .unwrap();
+            let solved = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+            assert!((solved - ytm).abs() < 1e-10);
+        }
+        // 求解区间内无解时返回错误, 而不是返回区间端点
+        let price = bond
+            .calc_dirty_price_with_ytm(0.45, date, None, None)
+            .unwrap();
+        let solver = YieldSolver::default().with_bracket(0., 0.3);
+        assert!(
+            bond.calc_ytm_with_solver(price, date, None, None, &solver)
+                .is_err()
+        );
+        assert!(bond.calc_ytm_with_price(5000., date, None, None).is_err());
+    }
 }
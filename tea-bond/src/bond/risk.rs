@@ -0,0 +1,221 @@
+use super::{Bond, RemainCashFlows};
+use crate::curve::{RateCurve, YieldCurve};
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+
+/// 基于曲线计算利率敏感性时的平移步长, 1bp
+const CURVE_BUMP: f64 = 1e-4;
+
+impl Bond {
+    /// 凸性
+    ///
+    /// 全价对收益率的二阶导数除以全价, 最后一个付息周期按单利折现计算
+    pub fn calc_convexity(
+        &self,
+        ytm: f64,
+        date: NaiveDate,
+        cp_dates: Option<(NaiveDate, NaiveDate)>,
+        remain_cp_num: Option<i32>,
+    ) -> Result<f64> {
+        let ytm = self.check_ytm(ytm);
+        if self.is_zero_coupon() {
+            return self.zero_coupon_convexity(ytm, date);
+        }
+        // 单利折现 P = F / (1 + y * t) 的凸性
+        let simple_convexity = |t: f64| 2. * t * t / (1. + ytm * t).powi(2);
+        let inst_freq = self.inst_freq as f64;
+        let RemainCashFlows {
+            flows,
+            remain_days,
+            period_days: ty,
+        } = self.remain_cash_flows(date, cp_dates, remain_cp_num)?;
+        let Some((_, last_period)) = flows.last() else {
+            return Ok(0.);
+        };
+        if *last_period == 0. {
+            return Ok(simple_convexity(
+                remain_days / self.last_period_year_days()?,
+            ));
+        }
+        let (pv, d2) = flows.iter().fold((0., 0.), |(pv, d2), (cf, period)| {
+            let n = remain_days / ty + period;
+            let discount_factor = (1. + ytm / inst_freq).powf(n);
+            (
+                pv + cf.amount() / discount_factor,
+                d2 + cf.amount() * n * (n + 1.)
+                    / (inst_freq * inst_freq * discount_factor * (1. + ytm / inst_freq).powi(2)),
+            )
+        });
+        Ok(d2 / pv)
+    }
+
+    /// 美元久期(金额久期) = 修正久期 * 全价
+    pub fn calc_dollar_duration(
+        &self,
+        ytm: f64,
+        date: NaiveDate,
+        cp_dates: Option<(NaiveDate, NaiveDate)>,
+        remain_cp_num: Option<i32>,
+    ) -> Result<f64> {
+        let duration = self.calc_duration(ytm, date, cp_dates, remain_cp_num)?;
+        let dirty_price = self.calc_dirty_price_with_ytm(ytm, date, cp_dates, remain_cp_num)?;
+        Ok(duration * dirty_price)
+    }
+
+    /// DV01, 收益率下降1bp时每百元面值全价的变动
+    #[inline]
+    pub fn calc_dv01(
+        &self,
+        ytm: f64,
+        date: NaiveDate,
+        cp_dates: Option<(NaiveDate, NaiveDate)>,
+        remain_cp_num: Option<i32>,
+    ) -> Result<f64> {
+        Ok(self.calc_dollar_duration(ytm, date, cp_dates, remain_cp_num)? * 1e-4)
+    }
+
+    /// 按即期收益率曲线对剩余现金流折现得到全价
+    ///
+    /// 现金流在实际支付日折现至计算日, 分期还本债券按每百元剩余本金报价
+    pub fn calc_dirty_price_with_curve(
+        &self,
+        curve: &impl RateCurve,
+        date: NaiveDate,
+    ) -> Result<f64> {
+        let schedule = self.cash_flow_schedule()?;
+        let discount_to_date = curve.discount_factor(date);
+        let pv = schedule
+            .remain(date)
+            .iter()
+            .map(|cf| cf.amount() * curve.discount_factor(cf.payment_date))
+            .sum::<f64>()
+            / discount_to_date;
+        Ok(pv / self.remaining_par_factor(date))
+    }
+
+    /// 相对曲线的贵贱程度
+    ///
+    /// 市场全价对应的到期收益率减去曲线定价对应的到期收益率, 为正表示债券相对曲线偏便宜
+    pub fn calc_rich_cheap(
+        &self,
+        curve: &impl RateCurve,
+        dirty_price: f64,
+        date: NaiveDate,
+    ) -> Result<f64> {
+        let model_price = self.calc_dirty_price_with_curve(curve, date)?;
+        let ytm = self.calc_ytm_with_price(dirty_price, date, None, None)?;
+        Ok(ytm - self.calc_ytm_with_price(model_price, date, None, None)?)
+    }
+
+    /// PV01, 即期收益率曲线整体下移1bp时每百元面值全价的变动(中心差分)
+    pub fn calc_pv01(&self, curve: &YieldCurve, date: NaiveDate) -> Result<f64> {
+        let price_down = self.calc_dirty_price_with_curve(&curve.shift(-CURVE_BUMP), date)?;
+        let price_up = self.calc_dirty_price_with_curve(&curve.shift(CURVE_BUMP), date)?;
+        Ok((price_down - price_up) / 2.)
+    }
+
+    /// 关键期限久期
+    ///
+    /// 依次对各关键期限的即期利率平移1bp(相邻关键期限之间线性衰减), 计算全价的相对变动,
+    /// 各关键期限久期之和约等于曲线平行平移下的有效久期
+    pub fn calc_key_rate_durations(
+        &self,
+        curve: &YieldCurve,
+        date: NaiveDate,
+        key_tenors: &[f64],
+    ) -> Result<Vec<f64>> {
+        ensure!(!key_tenors.is_empty(), "Key tenors should not be empty");
+        let price = self.calc_dirty_price_with_curve(curve, date)?;
+        (0..key_tenors.len())
+            .map(|i| self.key_rate_duration_with_price(curve, date, key_tenors, i, price))
+            .collect()
+    }
+
+    /// 第idx个关键期限的关键期限久期
+    pub fn calc_key_rate_duration(
+        &self,
+        curve: &YieldCurve,
+        date: NaiveDate,
+        key_tenors: &[f64],
+        idx: usize,
+    ) -> Result<f64> {
+        let price = self.calc_dirty_price_with_curve(curve, date)?;
+        self.key_rate_duration_with_price(curve, date, key_tenors, idx, price)
+    }
+
+    fn key_rate_duration_with_price(
+        &self,
+        curve: &YieldCurve,
+        date: NaiveDate,
+        key_tenors: &[f64],
+        idx: usize,
+        price: f64,
+    ) -> Result<f64> {
+        let down = curve.key_rate_shift(key_tenors, idx, -CURVE_BUMP)?;
+        let up = curve.key_rate_shift(key_tenors, idx, CURVE_BUMP)?;
+        let price_down = self.calc_dirty_price_with_curve(&down, date)?;
+        let price_up = self.calc_dirty_price_with_curve(&up, date)?;
+        Ok((price_down - price_up) / (2. * CURVE_BUMP * price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Market;
+
+    #[test]
+    fn test_bond_risk() {
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.025,
+            inst_freq: 1,
+            carry_date: NaiveDate::from_ymd_opt(2024, 5, 25).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2034, 5, 25).unwrap(),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 2, 17).unwrap();
+        let ytm = 0.018;
+        let price = |y: f64| bond.calc_dirty_price_with_ytm(y, date, None, None).unwrap();
+        let (p, h) = (price(ytm), 1e-4);
+        // 解析凸性与数值二阶导数一致
+        let convexity = bond.calc_convexity(ytm, date, None, None).unwrap();
+        let numeric = (price(ytm + h) + price(ytm - h) - 2. * p) / (h * h * p);
+        assert!((convexity - numeric).abs() < 1e-3 * convexity);
+        let dv01 = bond.calc_dv01(ytm, date, None, None).unwrap();
+        assert!((dv01 - (price(ytm - h) - price(ytm + h)) / 2.).abs() < 1e-6);
+        let duration = bond.calc_duration(ytm, date, None, None).unwrap();
+        let dollar_duration = bond.calc_dollar_duration(ytm, date, None, None).unwrap();
+        assert!((dollar_duration - duration * p).abs() < 1e-10);
+
+        // 水平曲线(年复利)下的曲线定价与收益率定价接近
+        let curve = YieldCurve::flat(date, ytm);
+        let curve_price = bond.calc_dirty_price_with_curve(&curve, date).unwrap();
+        assert!((curve_price - p).abs() < 0.05);
+        let pv01 = bond.calc_pv01(&curve, date).unwrap();
+        assert!((pv01 - dv01).abs() < 0.05 * dv01);
+        // 关键期限久期之和等于有效久期
+        let key_tenors = [1., 2., 5., 7., 10.];
+        let krd = bond
+            .calc_key_rate_durations(&curve, date, &key_tenors)
+            .unwrap();
+        assert_eq!(krd.len(), 5);
+        assert!((krd.iter().sum::<f64>() - pv01 / curve_price * 1e4).abs() < 1e-6);
+        // 到期期限附近的关键期限贡献最大
+        assert!(krd[4] > krd[3] && krd[3] > krd[0]);
+        let krd_7y = bond
+            .calc_key_rate_duration(&curve, date, &key_tenors, 3)
+            .unwrap();
+        assert_eq!(krd_7y, krd[3]);
+        assert!(
+            bond.calc_key_rate_duration(&curve, date, &key_tenors, 5)
+                .is_err()
+        );
+
+        // 最后一个付息周期
+        let date = NaiveDate::from_ymd_opt(2033, 12, 1).unwrap();
+        let t = 175. / 365.;
+        let convexity = bond.calc_convexity(ytm, date, None, None).unwrap();
+        assert!((convexity - 2. * t * t / (1. + ytm * t).powi(2)).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,240 @@
+use super::{Bond, CouponType, deserialize_date, serialize_date};
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 票面利率调整计划中的一项
+///
+/// 计息起始日不早于生效日的付息周期适用该票面利率, 直至下一项生效
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CouponRateStep {
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub start_date: NaiveDate, // 生效日
+    pub rate: f64, // 票面利率
+}
+
+impl CouponRateStep {
+    #[inline]
+    pub fn new(start_date: NaiveDate, rate: f64) -> Self {
+        Self { start_date, rate }
+    }
+}
+
+/// 分期还本计划中的一项
+///
+/// 在还本日偿还发行面值的`ratio`比例, 还本日应与付息日一致, 否则在所在付息周期结束时偿还
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PrincipalRepayment {
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub date: NaiveDate, // 还本日
+    pub ratio: f64, // 偿还本金占发行面值的比例
+}
+
+impl PrincipalRepayment {
+    #[inline]
+    pub fn new(date: NaiveDate, ratio: f64) -> Self {
+        Self { date, ratio }
+    }
+}
+
+impl Bond {
+    #[inline]
+    /// 是否为累进利率债券(票面利率分段调整)
+    pub fn is_progressive(&self) -> bool {
+        !self.cp_rate_schedule.is_empty()
+    }
+
+    /// 计息起始日为`accrual_start`的付息周期适用的票面利率
+    ///
+    /// 未设置票面利率调整计划或尚未到首个生效日时, 使用cp_rate
+    #[inline]
+    pub fn cp_rate_at(&self, accrual_start: NaiveDate) -> f64 {
+        self.cp_rate_schedule
+            .iter()
+            .filter(|step| step.start_date <= accrual_start)
+            .max_by_key(|step| step.start_date)
+            .map_or(self.cp_rate, |step| step.rate)
+    }
+
+    /// 计息起始日为`accrual_start`的付息周期的利息金额, 按期初剩余本金计息
+    #[inline]
+    pub fn period_coupon(&self, accrual_start: NaiveDate) -> f64 {
+        self.cp_rate_at(accrual_start) * self.remaining_par(accrual_start) / self.inst_freq as f64
+    }
+
+    #[inline]
+    /// 是否为分期还本债券
+    pub fn is_amortizing(&self) -> bool {
+        !self.amortization.is_empty()
+    }
+
+    /// 指定日期(含当日还本)之后的剩余本金
+    #[inline]
+    pub fn remaining_par(&self, date: NaiveDate) -> f64 {
+        let repaid: f64 = self
+            .amortization
+            .iter()
+            .filter(|r| r.date <= date)
+            .map(|r| r.ratio)
+            .sum();
+        self.par_value * (1. - repaid).max(0.)
+    }
+
+    /// 剩余本金占发行面值的比例
+    ///
+    /// 分期还本债券按每百元剩余本金报价, 全价与应计利息需要按该比例折算; 本金已全部偿还时不再折算
+    #[inline]
+    pub(crate) fn remaining_par_factor(&self, date: NaiveDate) -> f64 {
+        if !self.is_amortizing() {
+            return 1.;
+        }
+        let factor = self.remaining_par(date) / self.par_value;
+        if factor > 0. { factor } else { 1. }
+    }
+
+    /// 检查分期还本计划是否合法
+    pub(crate) fn check_amortization(&self) -> Result<()> {
+        if !self.is_amortizing() {
+            return Ok(());
+        }
+        ensure!(
+            self.cp_type == CouponType::CouponBear,
+            "Amortizing bond {} should be a coupon bearing bond",
+            self.code()
+        );
+        ensure!(
+            self.amortization
+                .iter()
+                .all(|r| r.ratio > 0. && r.date > self.carry_date && r.date <= self.maturity_date),
+            "Invalid amortization schedule of bond {}",
+            self.code()
+        );
+        let total: f64 = self.amortization.iter().map(|r| r.ratio).sum();
+        ensure!(
+            total <= 1. + 1e-8,
+            "Total amortization ratio {} of bond {} exceeds 1",
+            total,
+            self.code()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InterestType, Market};
+
+    #[test]
+    fn test_progressive_coupon() {
+        let bond = Bond {
+            mkt: Market::IB,
+            interest_type: InterestType::Progressive,
+            cp_rate: 0.03,
+            inst_freq: 1,
+            carry_date: NaiveDate::from_ymd_opt(2023, 4, 10).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2028, 4, 10).unwrap(),
+            cp_rate_schedule: vec![
+                CouponRateStep::new(NaiveDate::from_ymd_opt(2026, 4, 10).unwrap(), 0.04),
+                CouponRateStep::new(NaiveDate::from_ymd_opt(2025, 4, 10).unwrap(), 0.035),
+            ],
+            ..Default::default()
+        };
+        let coupons: Vec<f64> = bond
+            .cash_flow_schedule()
+            .unwrap()
+            .iter()
+            .map(|cf| cf.coupon)
+            .collect();
+        for (c, expect) in coupons.into_iter().zip([3., 3., 3.5, 4., 4.]) {
+            assert!((c - expect).abs() < 1e-12);
+        }
+
+        let date = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+        let ai = bond.calc_accrued_interest(date, None).unwrap();
+        assert!((ai - 3.5 * 183. / 365.).abs() < 1e-12);
+        // 与固定票面利率债券的定价结果不同, 且收益率可以还原
+        let price = bond
+            .calc_dirty_price_with_ytm(0.03, date, None, None)
+            .unwrap();
+        let fixed = Bond {
+            cp_rate_schedule: vec![],
+            ..bond.clone()
+        };
+        let fixed_price = fixed
+            .calc_dirty_price_with_ytm(0.03, date, None, None)
+            .unwrap();
+        assert!(price > fixed_price + 1.);
+        let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+        assert!((ytm - 0.03).abs() < 1e-8);
+
+        // json中未设置时默认为空
+        let bond: Bond = serde_json::from_str(r#"{"cp_rate": 0.0167}"#).unwrap();
+        assert!(!bond.is_progressive());
+        let json = r#"{"cp_rate_schedule": [{"start_date": "2025-04-10", "rate": 0.035}]}"#;
+        let bond: Bond = serde_json::from_str(json).unwrap();
+        assert_eq!(bond.cp_rate_schedule[0].rate, 0.035);
+    }
+
+    #[test]
+    fn test_amortizing_bond() {
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_rate: 0.04,
+            inst_freq: 1,
+            carry_date: NaiveDate::from_ymd_opt(2022, 4, 10).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2027, 4, 10).unwrap(),
+            amortization: [2025, 2026, 2027]
+                .into_iter()
+                .map(|y| PrincipalRepayment::new(NaiveDate::from_ymd_opt(y, 4, 10).unwrap(), 0.2))
+                .collect(),
+            ..Default::default()
+        };
+        // 最后一期偿还全部剩余本金
+        let schedule = bond.cash_flow_schedule().unwrap();
+        let principals: Vec<f64> = schedule.iter().map(|cf| cf.principal).collect();
+        let coupons: Vec<f64> = schedule.iter().map(|cf| cf.coupon).collect();
+        for (p, expect) in principals.into_iter().zip([0., 0., 20., 20., 60.]) {
+            assert!((p - expect).abs() < 1e-10);
+        }
+        for (c, expect) in coupons.into_iter().zip([4., 4., 4., 3.2, 2.4]) {
+            assert!((c - expect).abs() < 1e-10);
+        }
+
+        // 按每百元剩余本金计算应计利息与全价
+        let date = NaiveDate::from_ymd_opt(2025, 10, 10).unwrap();
+        assert!((bond.remaining_par(date) - 80.).abs() < 1e-10);
+        let ai = bond.calc_accrued_interest(date, None).unwrap();
+        assert!((ai - 4. * 183. / 365.).abs() < 1e-10);
+        let price = bond
+            .calc_dirty_price_with_ytm(0.04, date, None, None)
+            .unwrap();
+        let remain_year = 182. / 365.;
+        let pv =
+            (3.2 + 20.) / 1.04f64.powf(remain_year) + (2.4 + 60.) / 1.04f64.powf(remain_year + 1.);
+        assert!((price - pv / 0.8).abs() < 1e-10);
+        let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+        assert!((ytm - 0.04).abs() < 1e-8);
+        // 久期短于同期限的到期一次还本债券
+        let bullet = Bond {
+            amortization: vec![],
+            ..bond.clone()
+        };
+        assert!(
+            bond.calc_duration(0.04, date, None, None).unwrap()
+                < bullet.calc_duration(0.04, date, None, None).unwrap()
+        );
+
+        let invalid = Bond {
+            amortization: vec![PrincipalRepayment::new(bond.maturity_date, 1.2)],
+            ..bond
+        };
+        assert!(invalid.cash_flow_schedule().is_err());
+    }
+}
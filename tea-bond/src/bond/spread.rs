@@ -0,0 +1,135 @@
+use super::{Bond, BondYtm};
+use crate::YieldSolver;
+use crate::curve::RateCurve;
+use crate::day_counter::{ACTUAL, DayCountRule};
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+
+/// 即期利率整体加上固定利差后的曲线, 用于求解Z利差
+struct SpreadCurve<'a, C: RateCurve> {
+    curve: &'a C,
+    spread: f64,
+}
+
+impl<C: RateCurve> RateCurve for SpreadCurve<'_, C> {
+    #[inline]
+    fn ref_date(&self) -> NaiveDate {
+        self.curve.ref_date()
+    }
+
+    #[inline]
+    fn discount_factor_t(&self, t: f64) -> f64 {
+        (1. + self.curve.zero_rate_t(t) + self.spread).powf(-t)
+    }
+}
+
+impl Bond {
+    /// 到期收益率相对曲线上同剩余期限收益率的利差
+    #[inline]
+    fn yield_spread(&self, ytm: f64, curve: &impl RateCurve, date: NaiveDate) -> f64 {
+        let t = ACTUAL.count_days(date, self.maturity_date) as f64 / 365.;
+        self.check_ytm(ytm) - curve.zero_rate_t(t)
+    }
+
+    /// G利差, 到期收益率减去按剩余期限插值的国债收益率
+    ///
+    /// govt_curve为国债到期收益率曲线(关键期限对应国债收益率)
+    #[inline]
+    pub fn calc_g_spread(&self, ytm: f64, govt_curve: &impl RateCurve, date: NaiveDate) -> f64 {
+        self.yield_spread(ytm, govt_curve, date)
+    }
+
+    /// I利差, 到期收益率减去按剩余期限插值的利率互换利率
+    ///
+    /// swap_curve为利率互换曲线(关键期限对应互换利率)
+    #[inline]
+    pub fn calc_i_spread(&self, ytm: f64, swap_curve: &impl RateCurve, date: NaiveDate) -> f64 {
+        self.yield_spread(ytm, swap_curve, date)
+    }
+
+    /// Z利差, 即期利率曲线整体加上该利差后对剩余现金流折现等于全价
+    pub fn calc_z_spread(
+        &self,
+        dirty_price: f64,
+        curve: &impl RateCurve,
+        date: NaiveDate,
+    ) -> Result<f64> {
+        let price_with = |spread: f64| {
+            let curve = SpreadCurve { curve, spread };
+            self.calc_dirty_price_with_curve(&curve, date)
+        };
+        // 先行检查债券能否基于曲线定价
+        price_with(0.)?;
+        YieldSolver::default()
+            .with_bracket(-0.2, 1.)
+            .brent(|spread| price_with(spread).map_or(f64::NAN, |p| p - dirty_price))
+            .with_context(|| format!("Failed to solve z-spread of bond {}", self.code()))
+    }
+}
+
+impl BondYtm {
+    /// 按债券自身到期收益率计算G利差
+    #[inline]
+    pub fn calc_g_spread(&self, govt_curve: &impl RateCurve, date: NaiveDate) -> f64 {
+        self.bond.calc_g_spread(self.ytm(), govt_curve, date)
+    }
+
+    /// 按债券自身到期收益率计算I利差
+    #[inline]
+    pub fn calc_i_spread(&self, swap_curve: &impl RateCurve, date: NaiveDate) -> f64 {
+        self.bond.calc_i_spread(self.ytm(), swap_curve, date)
+    }
+
+    /// 按债券自身到期收益率对应的全价计算Z利差
+    pub fn calc_z_spread(&self, curve: &impl RateCurve, date: NaiveDate) -> Result<f64> {
+        let dirty_price = self.calc_dirty_price_with_ytm(self.ytm(), date, None, None)?;
+        self.bond.calc_z_spread(dirty_price, curve, date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Market, YieldCurve};
+
+    #[test]
+    fn test_spreads() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let bond = BondYtm::new(
+            Bond {
+                mkt: Market::IB,
+                cp_rate: 0.025,
+                inst_freq: 1,
+                carry_date: NaiveDate::from_ymd_opt(2024, 9, 3).unwrap(),
+                maturity_date: NaiveDate::from_ymd_opt(2029, 9, 3).unwrap(),
+                ..Default::default()
+            },
+            0.023,
+        );
+        let govt = YieldCurve::new(
+            date,
+            vec![1., 3., 5., 10.],
+            vec![0.015, 0.016, 0.017, 0.018],
+        )
+        .unwrap();
+        let t = 1645. / 365.;
+        let expect = 0.023 - (0.016 + 0.001 * (t - 3.) / 2.);
+        assert!((bond.calc_g_spread(&govt, date) - expect).abs() < 1e-12);
+        let swap = govt.shift(0.002);
+        assert!((bond.calc_i_spread(&swap, date) - expect + 0.002).abs() < 1e-12);
+
+        // 曲线平移Z利差后定价等于全价
+        let z = bond.calc_z_spread(&govt, date).unwrap();
+        let price = bond
+            .calc_dirty_price_with_ytm(bond.ytm(), date, None, None)
+            .unwrap();
+        let shifted = bond
+            .calc_dirty_price_with_curve(&govt.shift(z), date)
+            .unwrap();
+        assert!((shifted - price).abs() < 1e-8);
+        // 水平曲线下Z利差接近到期收益率与曲线利率之差
+        let flat = YieldCurve::flat(date, 0.02);
+        assert!((bond.calc_z_spread(&flat, date).unwrap() - 0.003).abs() < 2e-4);
+        assert!(bond.bond.calc_z_spread(1e5, &flat, date).is_err());
+    }
+}
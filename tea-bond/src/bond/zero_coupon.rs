@@ -0,0 +1,203 @@
+use super::{Bond, Market};
+use crate::day_counter::{ACTUAL, DayCountRule};
+use anyhow::{Result, ensure};
+use chrono::{Months, NaiveDate};
+
+impl Bond {
+    /// 贴现债券的剩余期限
+    ///
+    /// 以到期日为锚点逐年向前推算, 返回(计算日至下一周年日的天数 / 当前计息年度天数, 剩余整年数)
+    pub(crate) fn zero_coupon_remain_periods(&self, date: NaiveDate) -> Result<(f64, i32)> {
+        ensure!(
+            self.is_zero_coupon(),
+            "Bond {} is not a zero coupon bond",
+            self.code()
+        );
+        ensure!(
+            date < self.maturity_date,
+            "Zero coupon bond {} has matured at {}",
+            self.code(),
+            date
+        );
+        let mut years = 0;
+        let mut next = self.maturity_date;
+        let mut pre = next - Months::new(12);
+        while pre > date {
+            years += 1;
+            next = pre;
+            pre = next - Months::new(12);
+        }
+        let ty = match self.day_count.year_days() {
+            Some(year_days) => year_days,
+            None => ACTUAL.count_days(pre, next) as f64,
+        };
+        Ok((self.day_count.count_days(date, next) as f64 / ty, years))
+    }
+
+    /// 贴现债券剩余期限(年)
+    #[inline]
+    fn zero_coupon_remain_year(&self, date: NaiveDate) -> Result<f64> {
+        let (frac, years) = self.zero_coupon_remain_periods(date)?;
+        Ok(frac + years as f64)
+    }
+
+    /// 贴现债券通过收益率计算全价
+    ///
+    /// 剩余期限不超过一年按单利折现, 超过一年按年复利折现
+    pub(crate) fn zero_coupon_dirty_price(&self, ytm: f64, date: NaiveDate) -> Result<f64> {
+        let (frac, years) = self.zero_coupon_remain_periods(date)?;
+        let discount_factor = if years == 0 {
+            1. + ytm * frac
+        } else {
+            (1. + ytm).powf(frac + years as f64)
+        };
+        ensure!(
+            discount_factor > 0.,
+            "Invalid ytm {} of zero coupon bond {}",
+            ytm,
+            self.code()
+        );
+        Ok(self.par_value / discount_factor)
+    }
+
+    /// 贴现债券通过全价计算收益率
+    pub(crate) fn zero_coupon_ytm(&self, dirty_price: f64, date: NaiveDate) -> Result<f64> {
+        ensure!(
+            dirty_price > 0.,
+            "Dirty price of zero coupon bond {} should be positive",
+            self.code()
+        );
+        let (frac, years) = self.zero_coupon_remain_periods(date)?;
+        if years == 0 {
+            Ok((self.par_value / dirty_price - 1.) / frac)
+        } else {
+            Ok((self.par_value / dirty_price).powf(1. / (frac + years as f64)) - 1.)
+        }
+    }
+
+    /// 贴现债券的麦考利久期, 即剩余期限
+    #[inline]
+    pub(crate) fn zero_coupon_macaulay_duration(&self, date: NaiveDate) -> Result<f64> {
+        self.zero_coupon_remain_year(date)
+    }
+
+    /// 贴现债券的修正久期
+    pub(crate) fn zero_coupon_duration(&self, ytm: f64, date: NaiveDate) -> Result<f64> {
+        let (frac, years) = self.zero_coupon_remain_periods(date)?;
+        if years == 0 {
+            Ok(frac / (1. + ytm * frac))
+        } else {
+            Ok((frac + years as f64) / (1. + ytm))
+        }
+    }
+
+    /// 贴现债券的凸性
+    pub(crate) fn zero_coupon_convexity(&self, ytm: f64, date: NaiveDate) -> Result<f64> {
+        let (frac, years) = self.zero_coupon_remain_periods(date)?;
+        if years == 0 {
+            Ok(2. * frac * frac / (1. + ytm * frac).powi(2))
+        } else {
+            let t = frac + years as f64;
+            Ok(t * (t + 1.) / (1. + ytm).powi(2))
+        }
+    }
+
+    /// 贴现债券的应计利息
+    ///
+    /// 按发行价与面值之差在存续期内线性摊销, 交易所债券算头又算尾
+    pub(crate) fn zero_coupon_accrued_interest(&self, date: NaiveDate) -> f64 {
+        let extra_day = if self.mkt == Market::IB || date <= self.carry_date {
+            0
+        } else {
+            1
+        };
+        let total_days = self
+            .day_count
+            .count_days(self.carry_date, self.maturity_date);
+        let days = (self.day_count.count_days(self.carry_date, date) + extra_day).min(total_days);
+        if let Some(issue_price) = self.issue_price {
+            (self.par_value - issue_price) * days as f64 / total_days as f64
+        } else {
+            // 近似算法
+            let year_days = self.day_count.year_days().unwrap_or(365.);
+            self.cp_rate * self.par_value * days as f64 / year_days
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BondDayCount, CouponType};
+
+    #[test]
+    fn test_zero_coupon_pricing() {
+        // 3年期贴现债券
+        let bond = Bond {
+            mkt: Market::IB,
+            cp_type: CouponType::ZeroCoupon,
+            cp_rate: 0.,
+            inst_freq: 0,
+            carry_date: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+            maturity_date: NaiveDate::from_ymd_opt(2027, 3, 1).unwrap(),
+            day_count: BondDayCount::ActAct,
+            issue_price: Some(94.),
+            ..Default::default()
+        };
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let (frac, years) = bond.zero_coupon_remain_periods(date).unwrap();
+        assert_eq!(years, 2);
+        assert!((frac - 59. / 365.).abs() < 1e-12);
+        let price = bond
+            .calc_dirty_price_with_ytm(0.02, date, None, None)
+            .unwrap();
+        let t = 2. + 59. / 365.;
+        assert!((price - 100. / 1.02f64.powf(t)).abs() < 1e-10);
+        let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+        assert!((ytm - 0.02).abs() < 1e-12);
+        let duration = bond.calc_duration(0.02, date, None, None).unwrap();
+        assert!((duration - t / 1.02).abs() < 1e-12);
+        assert!((bond.calc_macaulay_duration(0.02, date, None, None).unwrap() - t).abs() < 1e-12);
+
+        // 剩余期限不超过一年按单利
+        let date = NaiveDate::from_ymd_opt(2026, 9, 1).unwrap();
+        let price = bond
+            .calc_dirty_price_with_ytm(0.02, date, None, None)
+            .unwrap();
+        let t = 181. / 365.;
+        assert!((price - 100. / (1. + 0.02 * t)).abs() < 1e-10);
+        let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+        assert!((ytm - 0.02).abs() < 1e-12);
+        let convexity = bond.calc_convexity(0.02, date, None, None).unwrap();
+        assert!((convexity - 2. * t * t / (1. + 0.02 * t).powi(2)).abs() < 1e-12);
+
+        // 应计利息按发行折价线性摊销, 交易所算头又算尾
+        let total = 1095.;
+        let ai = bond.calc_accrued_interest(date, None).unwrap();
+        assert!((ai - 6. * 914. / total).abs() < 1e-10);
+        let sh = Bond {
+            mkt: Market::SH,
+            ..bond.clone()
+        };
+        let ai = sh.calc_accrued_interest(date, None).unwrap();
+        assert!((ai - 6. * 915. / total).abs() < 1e-10);
+        let ai = sh.calc_accrued_interest(bond.maturity_date, None).unwrap();
+        assert!((ai - 6.).abs() < 1e-10);
+
+        // 到期后不能定价
+        let matured = bond.maturity_date;
+        assert!(
+            bond.calc_dirty_price_with_ytm(0.02, matured, None, None)
+                .is_err()
+        );
+        assert!(bond.calc_ytm_with_price(100., matured, None, None).is_err());
+        assert!(bond.calc_duration(0.02, matured, None, None).is_err());
+
+        // 非法价格返回错误而不是panic
+        assert!(bond.calc_ytm_with_price(0., date, None, None).is_err());
+        assert!(
+            bond.calc_dirty_price_with_ytm(-3., date, None, None)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use super::{Interpolation, YieldCurve};
+use crate::day_counter::{ACTUAL, DayCountRule};
+use crate::{Bond, BondDayCount, CouponType, YieldSolver};
+use anyhow::{Result, bail, ensure};
+use chrono::{Months, NaiveDate};
+use std::cell::RefCell;
+
+/// 非局部插值方法拔靴时的最大整体迭代次数
+const MAX_BOOTSTRAP_PASS: usize = 50;
+
+impl YieldCurve {
+    /// 由债券及其全价拔靴构造即期收益率曲线
+    ///
+    /// 以各债券的到期支付日作为关键期限, 按到期日从短到长依次求解使曲线定价等于全价的即期利率;
+    /// 非局部插值方法(三次样条、单调凸)在逐个求解后整体迭代直至收敛
+    pub fn bootstrap(
+        ref_date: NaiveDate,
+        instruments: &[(Bond, f64)],
+        interpolation: Interpolation,
+    ) -> Result<Self> {
+        // 关键期限取最后一笔现金流的实际支付日, 使局部插值下后续关键期限不影响已求解的债券
+        let mut instruments: Vec<(&Bond, f64, f64)> = instruments
+            .iter()
+            .filter(|(bond, _)| bond.maturity_date > ref_date)
+            .map(|(bond, price)| {
+                let schedule = bond.cash_flow_schedule()?;
+                let last_payment = schedule
+                    .last()
+                    .map_or(bond.maturity_date, |cf| cf.payment_date);
+                let t = ACTUAL.count_days(ref_date, last_payment) as f64 / 365.;
+                Ok((bond, *price, t))
+            })
+            .collect::<Result<_>>()?;
+        ensure!(
+            !instruments.is_empty(),
+            "No bond matures after the curve reference date {}",
+            ref_date
+        );
+        instruments.sort_by(|a, b| a.2.total_cmp(&b.2));
+        ensure!(
+            instruments.windows(2).all(|w| w[0].2 < w[1].2),
+            "Bonds used to bootstrap the curve should have different maturity dates"
+        );
+        let mut curve = Self::new_unchecked(
+            ref_date,
+            Vec::with_capacity(instruments.len()),
+            Vec::with_capacity(instruments.len()),
+            interpolation,
+        );
+        for (i, (bond, price, t)) in instruments.iter().enumerate() {
+            curve.tenors.push(*t);
+            let guess = curve.zero_rates.last().copied().unwrap_or(0.02);
+            curve.zero_rates.push(guess);
+            curve.update_coefs();
+            let rate = curve.solve_knot(i, bond, *price)?;
+            curve.set_zero_rate(i, rate);
+        }
+        if interpolation.is_local() {
+            return Ok(curve);
+        }
+        for _ in 0..MAX_BOOTSTRAP_PASS {
+            let mut max_diff: f64 = 0.;
+            for (i, (bond, price, _)) in instruments.iter().enumerate() {
+                let rate = curve.solve_knot(i, bond, *price)?;
+                max_diff = max_diff.max((rate - curve.zero_rates[i]).abs());
+                curve.set_zero_rate(i, rate);
+            }
+            if max_diff < 1e-12 {
+                return Ok(curve);
+            }
+        }
+        bail!(
+            "Bootstrapping with {:?} interpolation did not converge in {} passes",
+            interpolation,
+            MAX_BOOTSTRAP_PASS
+        )
+    }
+
+    /// 由关键期限的平价收益率拔靴构造即期收益率曲线
+    ///
+    /// 期限不超过一年按单利贴现债券处理, 超过一年按年付息inst_freq次、以平价发行的附息债券处理,
+    /// 期限需为付息间隔的整数倍
+    pub fn bootstrap_par_yields(
+        ref_date: NaiveDate,
+        tenors: &[f64],
+        par_yields: &[f64],
+        inst_freq: i32,
+        interpolation: Interpolation,
+    ) -> Result<Self> {
+        ensure!(
+            tenors.len() == par_yields.len(),
+            "Length of tenors {} and par yields {} mismatch",
+            tenors.len(),
+            par_yields.len()
+        );
+        ensure!(
+            matches!(inst_freq, 1 | 2 | 4),
+            "Invalid inst_freq: {}",
+            inst_freq
+        );
+        let instruments = tenors
+            .iter()
+            .zip(par_yields)
+            .map(|(&t, &y)| {
+                let months = (t * 12.).round();
+                ensure!(
+                    months > 0. && (t * 12. - months).abs() < 1e-6,
+                    "Tenor {} should be a positive whole number of months",
+                    t
+                );
+                let months = months as u32;
+                let mut bond = Bond {
+                    cp_rate: y,
+                    inst_freq,
+                    carry_date: ref_date,
+                    maturity_date: ref_date + Months::new(months),
+                    day_count: BondDayCount::ActAct,
+                    ..Default::default()
+                };
+                if months <= 12 {
+                    bond.cp_type = CouponType::ZeroCoupon;
+                    let price = bond.calc_dirty_price_with_ytm(y, ref_date, None, None)?;
+                    return Ok((bond, price));
+                }
+                ensure!(
+                    months.is_multiple_of(12 / inst_freq as u32),
+                    "Tenor {} is not a multiple of the coupon period",
+                    t
+                );
+                Ok((bond, 100.))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::bootstrap(ref_date, &instruments, interpolation)
+    }
+
+    /// 其余关键期限不变, 求解第i个关键期限的即期利率使债券的曲线定价等于全价
+    fn solve_knot(&self, i: usize, bond: &Bond, dirty_price: f64) -> Result<f64> {
+        // 复用同一条曲线, 每步只重新计算插值系数
+        let scratch = RefCell::new(self.clone());
+        let price_with = |rate: f64| {
+            let mut curve = scratch.borrow_mut();
+            curve.set_zero_rate(i, rate);
+            bond.calc_dirty_price_with_curve(&*curve, self.ref_date)
+        };
+        // 先行检查债券能否基于曲线定价
+        price_with(self.zero_rates[i])?;
+        YieldSolver::default()
+            .with_bracket(-0.2, 1.)
+            .brent(|rate| price_with(rate).map_or(f64::NAN, |p| p - dirty_price))
+            .map_err(|e| {
+                e.context(format!(
+                    "Failed to bootstrap zero rate at maturity of bond {}",
+                    bond.code()
+                ))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Market;
+
+    #[test]
+    fn test_bootstrap() {
+        let ref_date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let tenors = [0.5, 1., 2., 3., 5., 7., 10.];
+        let par_yields = [0.014, 0.0145, 0.0152, 0.0158, 0.0165, 0.0172, 0.018];
+        for interpolation in [
+            Interpolation::Linear,
+            Interpolation::LogLinear,
+            Interpolation::Cubic,
+            Interpolation::MonotoneConvex,
+        ] {
+            let curve =
+                YieldCurve::bootstrap_par_yields(ref_date, &tenors, &par_yields, 1, interpolation)
+                    .unwrap();
+            assert_eq!(curve.interpolation, interpolation);
+            assert_eq!(curve.tenors.len(), tenors.len());
+            // 平价债券按曲线定价等于面值
+            let bond = Bond {
+                mkt: Market::IB,
+                cp_rate: 0.0165,
+                carry_date: ref_date,
+                maturity_date: ref_date + Months::new(60),
+                ..Default::default()
+            };
+            let price = bond.calc_dirty_price_with_curve(&curve, ref_date).unwrap();
+            assert!(
+                (price - 100.).abs() < 1e-8,
+                "{interpolation:?} {price} {curve:?}"
+            );
+            // 正斜率的平价曲线对应的即期利率高于平价收益率
+            assert!(curve.zero_rates[6] > 0.018 && curve.zero_rates[6] < 0.0185);
+        }
+
+        // 由非平价的附息债券拔靴, 对原债券的定价误差为0
+        let bonds: Vec<(Bond, f64)> = [(2027, 0.021, 101.2), (2030, 0.024, 103.5)]
+            .into_iter()
+            .map(|(y, cp_rate, price)| {
+                let bond = Bond {
+                    mkt: Market::IB,
+                    cp_rate,
+                    inst_freq: 1,
+                    carry_date: NaiveDate::from_ymd_opt(y - 5, 6, 15).unwrap(),
+                    maturity_date: NaiveDate::from_ymd_opt(y, 6, 15).unwrap(),
+                    ..Default::default()
+                };
+                (bond, price)
+            })
+            .collect();
+        let curve = YieldCurve::bootstrap(ref_date, &bonds, Interpolation::MonotoneConvex).unwrap();
+        for (bond, price) in &bonds {
+            let p = bond.calc_dirty_price_with_curve(&curve, ref_date).unwrap();
+            assert!((p - price).abs() < 1e-8);
+            assert!(
+                bond.calc_rich_cheap(&curve, *price, ref_date)
+                    .unwrap()
+                    .abs()
+                    < 1e-8
+            );
+        }
+        // 价格低于曲线定价的债券相对偏便宜
+        let (bond, price) = &bonds[1];
+        assert!(bond.calc_rich_cheap(&curve, price - 0.5, ref_date).unwrap() > 0.);
+        assert!(YieldCurve::bootstrap(ref_date, &[], Interpolation::Linear).is_err());
+        assert!(
+            YieldCurve::bootstrap_par_yields(ref_date, &[1.5], &[0.02], 1, Interpolation::Linear)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// 曲线插值方法
+///
+/// 关键期限之间的插值方式, 首尾关键期限以外均按即期利率水平外推
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum Interpolation {
+    // 即期利率线性插值
+    #[default]
+    #[serde(alias = "linear")]
+    Linear,
+    // 贴现因子对数线性插值(分段常数远期利率)
+    #[serde(alias = "log_linear")]
+    LogLinear,
+    // 即期利率自然三次样条插值
+    #[serde(alias = "cubic")]
+    Cubic,
+    // 单调凸插值(Hagan-West), 保证远期利率连续且不产生额外震荡
+    #[serde(alias = "monotone_convex")]
+    MonotoneConvex,
+}
+
+impl FromStr for Interpolation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Linear" | "linear" => Ok(Interpolation::Linear),
+            "LogLinear" | "log_linear" | "loglinear" => Ok(Interpolation::LogLinear),
+            "Cubic" | "cubic" | "cubic_spline" => Ok(Interpolation::Cubic),
+            "MonotoneConvex" | "monotone_convex" => Ok(Interpolation::MonotoneConvex),
+            _ => anyhow::bail!("Unknown interpolation: {}", s),
+        }
+    }
+}
+
+/// 插值系数, 构建曲线时预先计算一次, 插值时只需定位所在区间
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct InterpCoefs {
+    m: Vec<f64>,  // 三次样条各关键期限处的二阶导数
+    fd: Vec<f64>, // 单调凸插值的离散远期利率, fd[k]对应补充0期限后第k-1至第k个期限的区间
+    f: Vec<f64>,  // 单调凸插值补充0期限后各期限处的瞬时远期利率
+}
+
+impl Interpolation {
+    /// 插值结果是否只依赖相邻的两个关键期限
+    ///
+    /// 非局部插值方法在增加关键期限后会改变已有区间的插值结果, 拔靴时需要整体迭代
+    #[inline]
+    pub fn is_local(&self) -> bool {
+        matches!(self, Interpolation::Linear | Interpolation::LogLinear)
+    }
+
+    /// 在关键期限(tenors, 严格递增)上插值得到期限为t年的即期利率(年复利)
+    ///
+    /// 每次调用都会重新计算插值系数, 多次插值请使用`YieldCurve`
+    pub fn interp(&self, tenors: &[f64], zero_rates: &[f64], t: f64) -> f64 {
+        let mut coefs = InterpCoefs::default();
+        self.update_coefs(tenors, zero_rates, &mut coefs);
+        self.interp_with(tenors, zero_rates, &coefs, t)
+    }
+
+    /// 计算插值系数, 复用coefs已分配的空间
+    pub(crate) fn update_coefs(&self, tenors: &[f64], zero_rates: &[f64], coefs: &mut InterpCoefs) {
+        coefs.m.clear();
+        coefs.fd.clear();
+        coefs.f.clear();
+        match self {
+            Interpolation::Cubic => cubic_coefs(tenors, zero_rates, &mut coefs.m),
+            Interpolation::MonotoneConvex => {
+                monotone_convex_coefs(tenors, zero_rates, &mut coefs.fd, &mut coefs.f)
+            }
+            Interpolation::Linear | Interpolation::LogLinear => {}
+        }
+    }
+
+    /// 使用预先计算的插值系数插值
+    pub(crate) fn interp_with(
+        &self,
+        tenors: &[f64],
+        zero_rates: &[f64],
+        coefs: &InterpCoefs,
+        t: f64,
+    ) -> f64 {
+        let n = tenors.len();
+        if t <= tenors[0] {
+            return zero_rates[0];
+        } else if t >= tenors[n - 1] {
+            return zero_rates[n - 1];
+        }
+        let i = tenors.partition_point(|x| *x <= t);
+        match self {
+            Interpolation::Linear => linear(tenors, zero_rates, i, t),
+            Interpolation::LogLinear => log_linear(tenors, zero_rates, i, t),
+            Interpolation::Cubic => cubic(tenors, zero_rates, &coefs.m, i, t),
+            Interpolation::MonotoneConvex => {
+                monotone_convex(tenors, zero_rates, &coefs.fd, &coefs.f, i, t)
+            }
+        }
+    }
+}
+
+#[inline]
+fn linear(tenors: &[f64], zero_rates: &[f64], i: usize, t: f64) -> f64 {
+    let (t0, t1) = (tenors[i - 1], tenors[i]);
+    let (r0, r1) = (zero_rates[i - 1], zero_rates[i]);
+    r0 + (r1 - r0) * (t - t0) / (t1 - t0)
+}
+
+fn log_linear(tenors: &[f64], zero_rates: &[f64], i: usize, t: f64) -> f64 {
+    let (t0, t1) = (tenors[i - 1], tenors[i]);
+    // ln(DF) = -t * ln(1 + r)
+    let (l0, l1) = (-t0 * zero_rates[i - 1].ln_1p(), -t1 * zero_rates[i].ln_1p());
+    let l = l0 + (l1 - l0) * (t - t0) / (t1 - t0);
+    (-l / t).exp_m1()
+}
+
+/// 自然三次样条各关键期限处的二阶导数, 端点二阶导数为0, 关键期限少于3个时为空
+fn cubic_coefs(tenors: &[f64], zero_rates: &[f64], m: &mut Vec<f64>) {
+    let n = tenors.len();
+    if n < 3 {
+        return;
+    }
+    // 追赶法求解
+    let h = |k: usize| tenors[k + 1] - tenors[k];
+    let mut c = vec![0.; n];
+    let mut d = vec![0.; n];
+    for k in 1..n - 1 {
+        let rhs = 6.
+            * ((zero_rates[k + 1] - zero_rates[k]) / h(k)
+                - (zero_rates[k] - zero_rates[k - 1]) / h(k - 1));
+        let diag = 2. * (h(k - 1) + h(k)) - h(k - 1) * c[k - 1];
+        c[k] = h(k) / diag;
+        d[k] = (rhs - h(k - 1) * d[k - 1]) / diag;
+    }
+    m.resize(n, 0.);
+    for k in (1..n - 1).rev() {
+        m[k] = d[k] - c[k] * m[k + 1];
+    }
+}
+
+/// 自然三次样条
+fn cubic(tenors: &[f64], zero_rates: &[f64], m: &[f64], i: usize, t: f64) -> f64 {
+    if m.is_empty() {
+        return linear(tenors, zero_rates, i, t);
+    }
+    let (t0, t1) = (tenors[i - 1], tenors[i]);
+    let hi = t1 - t0;
+    let (a, b) = ((t1 - t) / hi, (t - t0) / hi);
+    a * zero_rates[i - 1]
+        + b * zero_rates[i]
+        + ((a.powi(3) - a) * m[i - 1] + (b.powi(3) - b) * m[i]) * hi * hi / 6.
+}
+
+/// 在0期限处补充与首个关键期限相同的即期利率后, 第k个期限及其连续复利即期利率
+#[inline]
+fn padded_knot(tenors: &[f64], zero_rates: &[f64], k: usize) -> (f64, f64) {
+    match k {
+        0 => (0., zero_rates[0].ln_1p()),
+        _ => (tenors[k - 1], zero_rates[k - 1].ln_1p()),
+    }
+}
+
+/// 单调凸插值的离散远期利率及关键期限处的瞬时远期利率
+fn monotone_convex_coefs(tenors: &[f64], zero_rates: &[f64], fd: &mut Vec<f64>, f: &mut Vec<f64>) {
+    let n = tenors.len();
+    if n == 0 {
+        return;
+    }
+    let knot = |k| padded_knot(tenors, zero_rates, k);
+    fd.push(0.);
+    fd.extend((1..=n).map(|k| {
+        let ((t0, r0), (t1, r1)) = (knot(k - 1), knot(k));
+        (r1 * t1 - r0 * t0) / (t1 - t0)
+    }));
+    f.resize(n + 1, 0.);
+    for k in 1..n {
+        let ((t0, _), (t1, _), (t2, _)) = (knot(k - 1), knot(k), knot(k + 1));
+        f[k] = ((t1 - t0) * fd[k + 1] + (t2 - t1) * fd[k]) / (t2 - t0);
+    }
+    f[0] = fd[1] - 0.5 * (f[1] - fd[1]);
+    f[n] = fd[n] - 0.5 * (f[n - 1] - fd[n]);
+}
+
+/// Hagan-West单调凸插值
+///
+/// 在连续复利下对离散远期利率构造瞬时远期利率曲线, 积分得到即期利率, 关键期限处的即期利率保持不变
+fn monotone_convex(
+    tenors: &[f64],
+    zero_rates: &[f64],
+    fd: &[f64],
+    f: &[f64],
+    i: usize,
+    t: f64,
+) -> f64 {
+    // 原关键期限下标i对应补充0期限后的下标i+1
+    let k = i + 1;
+    let ((t0, r0), (t1, _)) = (
+        padded_knot(tenors, zero_rates, k - 1),
+        padded_knot(tenors, zero_rates, k),
+    );
+    let x = (t - t0) / (t1 - t0);
+    let (g0, g1) = (f[k - 1] - fd[k], f[k] - fd[k]);
+    let integral = if g0 == 0. && g1 == 0. {
+        0.
+    } else if (g0 < 0. && -0.5 * g0 <= g1 && g1 <= -2. * g0)
+        || (g0 > 0. && -0.5 * g0 >= g1 && g1 >= -2. * g0)
+    {
+        g0 * (x - 2. * x * x + x.powi(3)) + g1 * (x.powi(3) - x * x)
+    } else if (g0 < 0. && g1 > -2. * g0) || (g0 > 0. && g1 < -2. * g0) {
+        let eta = (g1 + 2. * g0) / (g1 - g0);
+        if x <= eta {
+            g0 * x
+        } else {
+            g0 * x + (g1 - g0) * (x - eta).powi(3) / (3. * (1. - eta).powi(2))
+        }
+    } else if (g0 > 0. && 0. > g1 && g1 > -0.5 * g0) || (g0 < 0. && 0. < g1 && g1 < -0.5 * g0) {
+        let eta = 3. * g1 / (g1 - g0);
+        if x < eta {
+            g1 * x + (g0 - g1) * (eta - (eta - x).powi(3) / (eta * eta)) / 3.
+        } else {
+            g1 * x + (g0 - g1) * eta / 3.
+        }
+    } else {
+        let eta = g1 / (g1 + g0);
+        let a = -g0 * g1 / (g0 + g1);
+        if x <= eta {
+            a * x + (g0 - a) * (eta - (eta - x).powi(3) / (eta * eta)) / 3.
+        } else {
+            a * x + (g0 - a) * eta / 3. + (g1 - a) * (x - eta).powi(3) / (3. * (1. - eta).powi(2))
+        }
+    };
+    let r = (r0 * t0 + fd[k] * (t - t0) + (t1 - t0) * integral) / t;
+    r.exp_m1()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolation() {
+        let tenors = [0.5, 1., 2., 5., 10.];
+        let zero_rates = [0.014, 0.015, 0.0155, 0.017, 0.0185];
+        for method in [
+            Interpolation::Linear,
+            Interpolation::LogLinear,
+            Interpolation::Cubic,
+            Interpolation::MonotoneConvex,
+        ] {
+            // 关键期限处还原即期利率, 两端水平外推
+            for (t, r) in tenors.iter().zip(zero_rates) {
+                assert!((method.interp(&tenors, &zero_rates, *t) - r).abs() < 1e-12);
+                assert!((method.interp(&tenors, &zero_rates, t + 1e-9) - r).abs() < 1e-8);
+            }
+            assert_eq!(method.interp(&tenors, &zero_rates, 0.1), 0.014);
+            assert_eq!(method.interp(&tenors, &zero_rates, 30.), 0.0185);
+            // 单调递增的关键期限利率插值后仍在相邻关键期限之间
+            for k in 1..100 {
+                let t = 0.5 + 9.5 * k as f64 / 100.;
+                let r = method.interp(&tenors, &zero_rates, t);
+                let i = tenors.partition_point(|x| *x <= t);
+                assert!(r >= zero_rates[i - 1] - 1e-4 && r <= zero_rates[i] + 1e-4);
+            }
+        }
+        // 对数线性插值的远期利率在关键期限之间为常数
+        let ln_df = |t: f64| {
+            -t * Interpolation::LogLinear
+                .interp(&tenors, &zero_rates, t)
+                .ln_1p()
+        };
+        let fwd = |t0: f64, t1: f64| (ln_df(t0) - ln_df(t1)) / (t1 - t0);
+        assert!((fwd(2.5, 3.) - fwd(4., 4.5)).abs() < 1e-12);
+        assert_eq!(
+            "monotone_convex".parse::<Interpolation>().unwrap(),
+            Interpolation::MonotoneConvex
+        );
+        assert!("spline".parse::<Interpolation>().is_err());
+    }
+}
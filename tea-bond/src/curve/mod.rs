@@ -0,0 +1,332 @@
+mod bootstrap;
+mod interpolation;
+mod nelson_siegel;
+
+use interpolation::InterpCoefs;
+pub use interpolation::Interpolation;
+pub use nelson_siegel::{FitObjective, NssCurve, NssFitOptions, NssFitResult, NssModel};
+
+use crate::bond::{deserialize_date, serialize_date};
+use crate::day_counter::{ACTUAL, DayCountRule};
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 利率曲线通用接口
+///
+/// 期限统一按 ACT/365 计算, 即期利率为年复利
+pub trait RateCurve {
+    /// 曲线基准日
+    fn ref_date(&self) -> NaiveDate;
+
+    /// 期限为t年的贴现因子
+    fn discount_factor_t(&self, t: f64) -> f64;
+
+    /// 基准日到指定日期的年化期限
+    #[inline]
+    fn year_fraction(&self, date: NaiveDate) -> f64 {
+        ACTUAL.count_days(self.ref_date(), date) as f64 / 365.
+    }
+
+    /// 指定日期的贴现因子
+    #[inline]
+    fn discount_factor(&self, date: NaiveDate) -> f64 {
+        self.discount_factor_t(self.year_fraction(date))
+    }
+
+    /// 期限为t年的即期利率(年复利)
+    #[inline]
+    fn zero_rate_t(&self, t: f64) -> f64 {
+        // 期限过短时用一天的期限近似, 避免除零
+        let t = t.max(1. / 365.);
+        self.discount_factor_t(t).powf(-1. / t) - 1.
+    }
+
+    /// 指定日期的即期利率(年复利)
+    #[inline]
+    fn zero_rate(&self, date: NaiveDate) -> f64 {
+        self.zero_rate_t(self.year_fraction(date))
+    }
+
+    /// 两个日期之间的远期利率(单利, ACT/365)
+    #[inline]
+    fn forward_rate(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        let tau = ACTUAL.count_days(start, end) as f64 / 365.;
+        if tau <= 0. {
+            return self.zero_rate(start);
+        }
+        (self.discount_factor(start) / self.discount_factor(end) - 1.) / tau
+    }
+}
+
+/// 即期收益率曲线
+///
+/// 由关键期限点的即期利率构成, 关键期限之间按插值方法插值(默认对即期利率线性插值), 两端水平外推;
+/// 插值系数在构建曲线时计算一次, 因此关键期限和即期利率只能通过构造函数设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "YieldCurveData")]
+pub struct YieldCurve {
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub ref_date: NaiveDate, // 曲线基准日
+    tenors: Vec<f64>,             // 关键期限(年)
+    zero_rates: Vec<f64>,         // 关键期限对应的即期利率(年复利)
+    interpolation: Interpolation, // 插值方法
+    #[serde(skip)]
+    coefs: InterpCoefs, // 插值系数
+}
+
+/// 反序列化时的曲线数据, 校验后计算插值系数
+#[derive(Deserialize)]
+struct YieldCurveData {
+    #[serde(deserialize_with = "deserialize_date")]
+    ref_date: NaiveDate,
+    tenors: Vec<f64>,
+    zero_rates: Vec<f64>,
+    #[serde(default)]
+    interpolation: Interpolation,
+}
+
+impl TryFrom<YieldCurveData> for YieldCurve {
+    type Error = anyhow::Error;
+
+    #[inline]
+    fn try_from(data: YieldCurveData) -> Result<Self> {
+        Ok(Self::new(data.ref_date, data.tenors, data.zero_rates)?
+            .with_interpolation(data.interpolation))
+    }
+}
+
+impl YieldCurve {
+    pub fn new(ref_date: NaiveDate, tenors: Vec<f64>, zero_rates: Vec<f64>) -> Result<Self> {
+        ensure!(
+            !tenors.is_empty(),
+            "Yield curve should have at least one tenor"
+        );
+        ensure!(
+            tenors.len() == zero_rates.len(),
+            "Length of tenors {} and zero rates {} mismatch",
+            tenors.len(),
+            zero_rates.len()
+        );
+        ensure!(
+            tenors.windows(2).all(|w| w[0] < w[1]),
+            "Tenors of yield curve should be strictly increasing"
+        );
+        Ok(Self::new_unchecked(
+            ref_date,
+            tenors,
+            zero_rates,
+            Interpolation::default(),
+        ))
+    }
+
+    /// 不校验关键期限, 直接计算插值系数构建曲线
+    fn new_unchecked(
+        ref_date: NaiveDate,
+        tenors: Vec<f64>,
+        zero_rates: Vec<f64>,
+        interpolation: Interpolation,
+    ) -> Self {
+        let mut curve = Self {
+            ref_date,
+            tenors,
+            zero_rates,
+            interpolation,
+            coefs: InterpCoefs::default(),
+        };
+        curve.update_coefs();
+        curve
+    }
+
+    #[inline]
+    fn update_coefs(&mut self) {
+        self.interpolation
+            .update_coefs(&self.tenors, &self.zero_rates, &mut self.coefs);
+    }
+
+    /// 设置插值方法
+    #[inline]
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        if self.interpolation != interpolation {
+            self.interpolation = interpolation;
+            self.update_coefs();
+        }
+        self
+    }
+
+    /// 关键期限(年)
+    #[inline]
+    pub fn tenors(&self) -> &[f64] {
+        &self.tenors
+    }
+
+    /// 关键期限对应的即期利率(年复利)
+    #[inline]
+    pub fn zero_rates(&self) -> &[f64] {
+        &self.zero_rates
+    }
+
+    /// 插值方法
+    #[inline]
+    pub fn interpolation(&self) -> Interpolation {
+        self.interpolation
+    }
+
+    /// 修改第i个关键期限的即期利率并重新计算插值系数
+    #[inline]
+    pub(crate) fn set_zero_rate(&mut self, i: usize, rate: f64) {
+        self.zero_rates[i] = rate;
+        self.update_coefs();
+    }
+
+    /// 水平曲线
+    #[inline]
+    pub fn flat(ref_date: NaiveDate, rate: f64) -> Self {
+        Self::new_unchecked(ref_date, vec![1.], vec![rate], Interpolation::default())
+    }
+
+    /// 曲线平移, shift为平移的利率(如0.0001为1bp)
+    #[inline]
+    pub fn shift(&self, shift: f64) -> Self {
+        Self::new_unchecked(
+            self.ref_date,
+            self.tenors.clone(),
+            self.zero_rates.iter().map(|r| r + shift).collect(),
+            self.interpolation,
+        )
+    }
+
+    /// 关键期限利率平移
+    ///
+    /// 对第idx个关键期限的即期利率平移shift, 相邻关键期限之间按三角形权重线性衰减,
+    /// 首尾关键期限以外水平延伸, 各关键期限平移之和等于整条曲线平行平移;
+    /// 非线性插值的曲线在合并后的期限点上平移, 期限点之间按原插值方法过渡
+    pub fn key_rate_shift(&self, key_tenors: &[f64], idx: usize, shift: f64) -> Result<Self> {
+        ensure!(
+            idx < key_tenors.len(),
+            "Key tenor index {} out of range {}",
+            idx,
+            key_tenors.len()
+        );
+        ensure!(
+            key_tenors.windows(2).all(|w| w[0] < w[1]),
+            "Key tenors should be strictly increasing"
+        );
+        let weight = |t: f64| {
+            let k = key_tenors[idx];
+            if t < k {
+                match idx.checked_sub(1).map(|i| key_tenors[i]) {
+                    Some(prev) if t <= prev => 0.,
+                    Some(prev) => (t - prev) / (k - prev),
+                    None => 1.,
+                }
+            } else {
+                match key_tenors.get(idx + 1) {
+                    Some(&next) if t >= next => 0.,
+                    Some(&next) => (next - t) / (next - k),
+                    None => 1.,
+                }
+            }
+        };
+        // 平移后的曲线在原期限与关键期限处均为折点, 合并后线性插值可精确表示
+        let mut tenors: Vec<f64> = self.tenors.iter().chain(key_tenors).copied().collect();
+        tenors.sort_by(f64::total_cmp);
+        tenors.dedup();
+        let zero_rates = tenors
+            .iter()
+            .map(|&t| self.interp_zero_rate(t) + shift * weight(t))
+            .collect();
+        Ok(Self::new_unchecked(
+            self.ref_date,
+            tenors,
+            zero_rates,
+            self.interpolation,
+        ))
+    }
+
+    /// 按插值方法得到期限为t年的即期利率
+    #[inline]
+    pub fn interp_zero_rate(&self, t: f64) -> f64 {
+        self.interpolation
+            .interp_with(&self.tenors, &self.zero_rates, &self.coefs, t)
+    }
+}
+
+impl RateCurve for YieldCurve {
+    #[inline]
+    fn ref_date(&self) -> NaiveDate {
+        self.ref_date
+    }
+
+    #[inline]
+    fn discount_factor_t(&self, t: f64) -> f64 {
+        (1. + self.interp_zero_rate(t)).powf(-t)
+    }
+
+    #[inline]
+    fn zero_rate_t(&self, t: f64) -> f64 {
+        self.interp_zero_rate(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yield_curve() {
+        let ref_date = NaiveDate::from_ymd_opt(2025, 1, 2).unwrap();
+        let curve = YieldCurve::new(ref_date, vec![1., 5., 10.], vec![0.015, 0.02, 0.025]).unwrap();
+        assert_eq!(curve.interp_zero_rate(0.5), 0.015);
+        assert!((curve.interp_zero_rate(3.) - 0.0175).abs() < 1e-12);
+        assert_eq!(curve.interp_zero_rate(30.), 0.025);
+        let date = NaiveDate::from_ymd_opt(2029, 1, 2).unwrap();
+        let t = curve.year_fraction(date);
+        let r = 0.015 + 0.005 * (t - 1.) / 4.;
+        assert!((curve.discount_factor(date) - (1. + r).powf(-t)).abs() < 1e-12);
+        // 远期利率与贴现因子一致
+        let start = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        let fwd = curve.forward_rate(start, date);
+        let tau = ACTUAL.count_days(start, date) as f64 / 365.;
+        assert!(
+            (curve.discount_factor(start) / (1. + fwd * tau) - curve.discount_factor(date)).abs()
+                < 1e-12
+        );
+        assert!(YieldCurve::new(ref_date, vec![5., 1.], vec![0.02, 0.01]).is_err());
+
+        // 关键期限平移之和等于平行平移
+        let key_tenors = [2., 5., 7.];
+        let shifted: Vec<_> = (0..3)
+            .map(|i| curve.key_rate_shift(&key_tenors, i, 1e-4).unwrap())
+            .collect();
+        for t in [0.5, 2., 3.2, 5., 6., 8., 20.] {
+            let total: f64 = shifted
+                .iter()
+                .map(|c| c.interp_zero_rate(t) - curve.interp_zero_rate(t))
+                .sum();
+            assert!((total - 1e-4).abs() < 1e-12);
+        }
+        assert!(
+            (shifted[1].interp_zero_rate(3.5) - curve.interp_zero_rate(3.5) - 0.5e-4).abs() < 1e-12
+        );
+        assert!((shifted[1].interp_zero_rate(8.) - curve.interp_zero_rate(8.)).abs() < 1e-12);
+
+        // 预先计算的插值系数与逐次插值一致, 反序列化后重新计算
+        for method in [Interpolation::Cubic, Interpolation::MonotoneConvex] {
+            let curve = curve.clone().with_interpolation(method);
+            let json = serde_json::to_string(&curve).unwrap();
+            let loaded: YieldCurve = serde_json::from_str(&json).unwrap();
+            assert_eq!(loaded, curve);
+            for t in [0.5, 2., 3.2, 6., 9.9] {
+                let expect = method.interp(curve.tenors(), curve.zero_rates(), t);
+                assert_eq!(curve.interp_zero_rate(t), expect);
+                assert_eq!(loaded.interp_zero_rate(t), expect);
+            }
+        }
+        let json = r#"{"ref_date": "2025-01-02", "tenors": [5, 1], "zero_rates": [0.02, 0.01]}"#;
+        assert!(serde_json::from_str::<YieldCurve>(json).is_err());
+    }
+}
@@ -0,0 +1,451 @@
+use super::RateCurve;
+use crate::BondYtm;
+use crate::bond::{deserialize_date, serialize_date};
+use crate::day_counter::{ACTUAL, DayCountRule};
+use crate::solver::nelder_mead;
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// 参数化曲线模型
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum NssModel {
+    // Nelson-Siegel, 4个参数
+    #[default]
+    #[serde(alias = "nelson_siegel", alias = "NS")]
+    NelsonSiegel,
+    // Svensson, 在Nelson-Siegel基础上增加第二个驼峰项, 6个参数
+    #[serde(alias = "svensson", alias = "NSS")]
+    Svensson,
+}
+
+impl FromStr for NssModel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NelsonSiegel" | "nelson_siegel" | "NS" | "ns" => Ok(NssModel::NelsonSiegel),
+            "Svensson" | "svensson" | "NSS" | "nss" => Ok(NssModel::Svensson),
+            _ => anyhow::bail!("Unknown nss model: {}", s),
+        }
+    }
+}
+
+/// 拟合误差的度量方式
+#[derive(Debug, Default, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+pub enum FitObjective {
+    // 全价误差
+    #[serde(alias = "price")]
+    Price,
+    // 到期收益率误差
+    #[default]
+    #[serde(alias = "yield")]
+    Yield,
+}
+
+impl FromStr for FitObjective {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Price" | "price" => Ok(FitObjective::Price),
+            "Yield" | "yield" | "ytm" => Ok(FitObjective::Yield),
+            _ => anyhow::bail!("Unknown fit objective: {}", s),
+        }
+    }
+}
+
+/// 曲线拟合参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NssFitOptions {
+    pub model: NssModel,
+    pub objective: FitObjective,
+    // 是否按久期加权: 价格误差除以久期, 收益率误差乘以久期
+    pub duration_weighted: bool,
+    pub max_iter: usize, // 单次优化的最大迭代次数
+}
+
+impl Default for NssFitOptions {
+    fn default() -> Self {
+        Self {
+            model: NssModel::default(),
+            objective: FitObjective::default(),
+            duration_weighted: false,
+            max_iter: 3000,
+        }
+    }
+}
+
+/// Nelson-Siegel(-Svensson)参数化即期收益率曲线
+///
+/// r(t) = β0 + β1 * (1 - e^(-t/τ1)) / (t/τ1)
+///           + β2 * ((1 - e^(-t/τ1)) / (t/τ1) - e^(-t/τ1))
+///           + β3 * ((1 - e^(-t/τ2)) / (t/τ2) - e^(-t/τ2))
+///
+/// 与YieldCurve一致, 期限按ACT/365计算, 即期利率为年复利; Nelson-Siegel模型下β3为0
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NssCurve {
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub ref_date: NaiveDate, // 曲线基准日
+    pub model: NssModel,
+    pub beta0: f64, // 长期水平
+    pub beta1: f64, // 斜率
+    pub beta2: f64, // 曲率
+    pub beta3: f64, // 第二曲率(仅Svensson)
+    pub tau1: f64,
+    pub tau2: f64, // 仅Svensson
+}
+
+/// 曲线拟合结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct NssFitResult {
+    pub curve: NssCurve,
+    pub price_residuals: Vec<f64>, // 市场全价 - 模型全价, 为正表示偏贵
+    pub ytm_residuals: Vec<f64>,   // 市场收益率 - 模型收益率, 为正表示偏便宜
+    pub rmse: f64,                 // 按拟合误差度量方式及权重计算的均方根误差
+}
+
+impl NssCurve {
+    #[inline]
+    pub fn nelson_siegel(
+        ref_date: NaiveDate,
+        beta0: f64,
+        beta1: f64,
+        beta2: f64,
+        tau1: f64,
+    ) -> Self {
+        Self {
+            ref_date,
+            model: NssModel::NelsonSiegel,
+            beta0,
+            beta1,
+            beta2,
+            beta3: 0.,
+            tau1,
+            tau2: tau1,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn svensson(
+        ref_date: NaiveDate,
+        beta0: f64,
+        beta1: f64,
+        beta2: f64,
+        beta3: f64,
+        tau1: f64,
+        tau2: f64,
+    ) -> Self {
+        Self {
+            ref_date,
+            model: NssModel::Svensson,
+            beta0,
+            beta1,
+            beta2,
+            beta3,
+            tau1,
+            tau2,
+        }
+    }
+
+    /// 由优化变量构造曲线, τ以对数形式优化以保证为正
+    fn from_params(ref_date: NaiveDate, model: NssModel, x: &[f64]) -> Self {
+        match model {
+            NssModel::NelsonSiegel => Self::nelson_siegel(ref_date, x[0], x[1], x[2], x[3].exp()),
+            NssModel::Svensson => {
+                Self::svensson(ref_date, x[0], x[1], x[2], x[4], x[3].exp(), x[5].exp())
+            }
+        }
+    }
+
+    /// 曲线参数, Nelson-Siegel为[β0, β1, β2, τ1], Svensson为[β0, β1, β2, β3, τ1, τ2]
+    pub fn params(&self) -> Vec<f64> {
+        match self.model {
+            NssModel::NelsonSiegel => vec![self.beta0, self.beta1, self.beta2, self.tau1],
+            NssModel::Svensson => vec![
+                self.beta0, self.beta1, self.beta2, self.beta3, self.tau1, self.tau2,
+            ],
+        }
+    }
+
+    /// 期限为t年的即期利率(年复利)
+    pub fn rate(&self, t: f64) -> f64 {
+        // (1 - e^(-x)) / x 及其在0处的极限
+        let loading = |x: f64| {
+            if x < 1e-8 {
+                (1., 0.)
+            } else {
+                let e = (-x).exp();
+                let slope = (1. - e) / x;
+                (slope, slope - e)
+            }
+        };
+        let (slope, curvature) = loading(t / self.tau1);
+        let mut r = self.beta0 + self.beta1 * slope + self.beta2 * curvature;
+        if self.model == NssModel::Svensson {
+            r += self.beta3 * loading(t / self.tau2).1;
+        }
+        r
+    }
+
+    /// 用债券收益率拟合曲线
+    ///
+    /// 以加权最小二乘为目标, 从多组τ初值出发用单纯形法优化, 取误差最小的结果;
+    /// 收益率误差按久期由价格误差近似, 残差按输入债券的顺序返回
+    pub fn fit(
+        bonds: &[BondYtm],
+        date: NaiveDate,
+        options: &NssFitOptions,
+    ) -> Result<NssFitResult> {
+        let n_params = match options.model {
+            NssModel::NelsonSiegel => 4,
+            NssModel::Svensson => 6,
+        };
+        ensure!(
+            bonds.len() >= n_params,
+            "At least {} bonds are required to fit {:?} curve, got {}",
+            n_params,
+            options.model,
+            bonds.len()
+        );
+        let quotes = bonds
+            .iter()
+            .map(|b| BondQuote::new(b, date))
+            .collect::<Result<Vec<_>>>()?;
+        let weights: Vec<f64> = quotes
+            .iter()
+            .map(|q| match (options.duration_weighted, options.objective) {
+                (false, _) => 1.,
+                (true, FitObjective::Price) => 1. / q.duration.max(1e-2),
+                (true, FitObjective::Yield) => q.duration,
+            })
+            .collect();
+        // 按加权误差平方和的均值计算目标函数
+        let objective = |curve: &NssCurve| {
+            quotes
+                .iter()
+                .zip(&weights)
+                .map(|(q, w)| {
+                    let err = match options.objective {
+                        FitObjective::Price => q.model_price(curve) - q.dirty_price,
+                        FitObjective::Yield => {
+                            (q.model_price(curve) - q.dirty_price) / (q.duration * q.dirty_price)
+                        }
+                    };
+                    (w * err).powi(2)
+                })
+                .sum::<f64>()
+                / quotes.len() as f64
+        };
+
+        let (short, long) = quotes.iter().fold((&quotes[0], &quotes[0]), |(s, l), q| {
+            (if q.t < s.t { q } else { s }, if q.t > l.t { q } else { l })
+        });
+        let (beta0, beta1) = (long.ytm, short.ytm - long.ytm);
+        let starts: &[(f64, f64)] = match options.model {
+            NssModel::NelsonSiegel => &[(0.5, 0.), (1., 0.), (2., 0.), (5., 0.)],
+            NssModel::Svensson => &[(0.5, 3.), (1., 5.), (2., 8.), (1., 10.)],
+        };
+        let (x, value) = starts
+            .iter()
+            .map(|&(tau1, tau2)| {
+                let (x0, step) = match options.model {
+                    NssModel::NelsonSiegel => (
+                        vec![beta0, beta1, 0., f64::ln(tau1)],
+                        vec![0.01, 0.01, 0.01, 0.5],
+                    ),
+                    NssModel::Svensson => (
+                        vec![beta0, beta1, 0., f64::ln(tau1), 0., f64::ln(tau2)],
+                        vec![0.01, 0.01, 0.01, 0.5, 0.01, 0.5],
+                    ),
+                };
+                nelder_mead(
+                    |x| objective(&Self::from_params(date, options.model, x)),
+                    &x0,
+                    &step,
+                    1e-24,
+                    options.max_iter,
+                )
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        ensure!(value.is_finite(), "Failed to fit {:?} curve", options.model);
+        let curve = Self::from_params(date, options.model, &x);
+        let price_residuals = quotes
+            .iter()
+            .map(|q| q.dirty_price - q.model_price(&curve))
+            .collect();
+        let ytm_residuals = quotes
+            .iter()
+            .map(|q| q.model_ytm(&curve).map_or(f64::NAN, |y| q.ytm - y))
+            .collect();
+        Ok(NssFitResult {
+            curve,
+            price_residuals,
+            ytm_residuals,
+            rmse: value.sqrt(),
+        })
+    }
+}
+
+impl RateCurve for NssCurve {
+    #[inline]
+    fn ref_date(&self) -> NaiveDate {
+        self.ref_date
+    }
+
+    #[inline]
+    fn discount_factor_t(&self, t: f64) -> f64 {
+        (1. + self.rate(t)).powf(-t)
+    }
+
+    #[inline]
+    fn zero_rate_t(&self, t: f64) -> f64 {
+        self.rate(t)
+    }
+}
+
+/// 拟合所用的单只债券报价, 预先展开剩余现金流以避免重复计算
+struct BondQuote<'a> {
+    bond: &'a BondYtm,
+    date: NaiveDate,
+    ytm: f64,
+    dirty_price: f64,
+    duration: f64,
+    t: f64,                 // 剩余期限(年)
+    flows: Vec<(f64, f64)>, // (支付期限, 现金流金额)
+    par_factor: f64,        // 剩余本金比例
+}
+
+impl<'a> BondQuote<'a> {
+    fn new(bond: &'a BondYtm, date: NaiveDate) -> Result<Self> {
+        let ytm = bond.ytm();
+        ensure!(
+            ytm.is_finite(),
+            "Ytm of bond {} should be finite",
+            bond.code()
+        );
+        let year = |d: NaiveDate| ACTUAL.count_days(date, d) as f64 / 365.;
+        let schedule = bond.cash_flow_schedule()?;
+        let flows: Vec<(f64, f64)> = schedule
+            .remain(date)
+            .iter()
+            .map(|cf| (year(cf.payment_date), cf.amount()))
+            .collect();
+        ensure!(
+            !flows.is_empty(),
+            "Bond {} has no cash flow after {}",
+            bond.code(),
+            date
+        );
+        Ok(Self {
+            bond,
+            date,
+            ytm,
+            dirty_price: bond.calc_dirty_price_with_ytm(ytm, date, None, None)?,
+            duration: bond.calc_duration(ytm, date, None, None)?,
+            t: year(bond.maturity_date),
+            flows,
+            par_factor: bond.remaining_par_factor(date),
+        })
+    }
+
+    #[inline]
+    fn model_price(&self, curve: &NssCurve) -> f64 {
+        self.flows
+            .iter()
+            .map(|(t, amount)| amount * curve.discount_factor_t(*t))
+            .sum::<f64>()
+            / self.par_factor
+    }
+
+    #[inline]
+    fn model_ytm(&self, curve: &NssCurve) -> Result<f64> {
+        self.bond
+            .calc_ytm_with_price(self.model_price(curve), self.date, None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bond, Market};
+    use chrono::Months;
+
+    #[test]
+    fn test_nelson_siegel_fit() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        let truth = NssCurve::nelson_siegel(date, 0.022, -0.008, 0.004, 1.8);
+        assert!((truth.rate(0.) - 0.014).abs() < 1e-12);
+        assert!((truth.rate(100.) - 0.022).abs() < 1e-3);
+        // 按真实曲线定价得到的国债收益率
+        let bonds: Vec<BondYtm> = [
+            (1, 0.015),
+            (2, 0.017),
+            (3, 0.02),
+            (5, 0.021),
+            (7, 0.024),
+            (10, 0.023),
+            (15, 0.026),
+            (30, 0.025),
+        ]
+        .into_iter()
+        .map(|(years, cp_rate)| {
+            let bond = Bond {
+                mkt: Market::IB,
+                cp_rate,
+                inst_freq: 1,
+                carry_date: date - Months::new(5),
+                maturity_date: date - Months::new(5) + Months::new(12 * years),
+                ..Default::default()
+            };
+            let price = bond.calc_dirty_price_with_curve(&truth, date).unwrap();
+            let ytm = bond.calc_ytm_with_price(price, date, None, None).unwrap();
+            BondYtm::new(bond, ytm)
+        })
+        .collect();
+
+        for objective in [FitObjective::Yield, FitObjective::Price] {
+            let options = NssFitOptions {
+                objective,
+                duration_weighted: true,
+                ..Default::default()
+            };
+            let result = NssCurve::fit(&bonds, date, &options).unwrap();
+            assert_eq!(result.ytm_residuals.len(), bonds.len());
+            assert!(result.ytm_residuals.iter().all(|r| r.abs() < 1e-6));
+            assert!(result.price_residuals.iter().all(|r| r.abs() < 1e-4));
+            for t in [0.5, 2., 7., 20.] {
+                assert!((result.curve.rate(t) - truth.rate(t)).abs() < 1e-5);
+            }
+        }
+        // Svensson模型可以嵌套Nelson-Siegel模型
+        let options = NssFitOptions {
+            model: NssModel::Svensson,
+            ..Default::default()
+        };
+        let result = NssCurve::fit(&bonds, date, &options).unwrap();
+        assert_eq!(result.curve.params().len(), 6);
+        assert!(result.rmse < 1e-5);
+
+        // 偏离曲线的债券残差为正(偏便宜)
+        let mut bonds = bonds;
+        bonds[4] = bonds[4].clone().with_ytm(bonds[4].ytm() + 0.001);
+        let result = NssCurve::fit(&bonds, date, &NssFitOptions::default()).unwrap();
+        let cheapest = result
+            .ytm_residuals
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap()
+            .0;
+        assert_eq!(cheapest, 4);
+        assert!(result.price_residuals[4] < 0.);
+        assert!(NssCurve::fit(&bonds[..3], date, &NssFitOptions::default()).is_err());
+    }
+}
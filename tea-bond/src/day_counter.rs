@@ -1,14 +1,27 @@
 use chrono::{Datelike, NaiveDate};
+use tea_calendar::{
+    Calendar,
+    china::{IB, SSE},
+};
 
 /// Represents the Actual/Actual day count convention.
 pub const ACTUAL: Actual = Actual {};
 
+/// Represents the Actual day count convention excluding February 29th.
+pub const ACTUAL_NO_LEAP: ActualNoLeap = ActualNoLeap {};
+
 /// Represents the Thirty/360 day count convention.
 pub const THIRTY: Thirty = Thirty {};
 
 /// Represents the Business day count convention.
 pub const BUSINESS: Business = Business {};
 
+/// Represents the Business day count convention of the China interbank market.
+pub const IB_BUSINESS: CalendarBusiness<IB> = CalendarBusiness(IB);
+
+/// Represents the Business day count convention of the Shanghai Stock Exchange.
+pub const SSE_BUSINESS: CalendarBusiness<SSE> = CalendarBusiness(SSE);
+
 /// 计算两个日期之间的天数，不同规则有不同实现
 pub trait DayCountRule {
     fn count_days(&self, start: NaiveDate, end: NaiveDate) -> i64;
@@ -26,6 +39,26 @@ impl DayCountRule for Actual {
     }
 }
 
+/// Represents the Actual day count convention excluding February 29th.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ActualNoLeap {}
+
+impl DayCountRule for ActualNoLeap {
+    /// Calculates the actual number of days between two dates, February 29th is not counted.
+    /// The leap day is excluded when it falls in the interval (start, end].
+    #[inline]
+    fn count_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return -self.count_days(end, start);
+        }
+        let leap_days = (start.year()..=end.year())
+            .filter_map(|y| NaiveDate::from_ymd_opt(y, 2, 29))
+            .filter(|d| *d > start && *d <= end)
+            .count() as i64;
+        (end - start).num_days() - leap_days
+    }
+}
+
 /// Represents the Thirty/360 day count convention.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Thirty {}
@@ -69,6 +102,27 @@ impl DayCountRule for Business {
     }
 }
 
+/// Represents the Business day count convention based on a market calendar.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CalendarBusiness<C: Calendar>(pub C);
+
+impl<C: Calendar> DayCountRule for CalendarBusiness<C> {
+    /// Calculates the number of business days in the interval (start, end] according to the calendar.
+    /// Holidays and weekends are excluded, while working weekends are included.
+    #[inline]
+    fn count_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return -self.count_days(end, start);
+        }
+        start
+            .iter_days()
+            .skip(1)
+            .take_while(|d| *d <= end)
+            .filter(|d| self.0.is_business_day(*d))
+            .count() as i64
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +164,27 @@ mod tests {
         let end = NaiveDate::from_ymd_opt(2023, 3, 20).unwrap();
         assert_eq!(BUSINESS.count_days(start, end), 53);
     }
+
+    #[test]
+    fn test_actual_no_leap_count_days() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        assert_eq!(ACTUAL_NO_LEAP.count_days(start, end), 365);
+        assert_eq!(ACTUAL_NO_LEAP.count_days(end, start), -365);
+
+        let start = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(ACTUAL_NO_LEAP.count_days(start, end), 1);
+    }
+
+    #[test]
+    fn test_calendar_business_count_days() {
+        // 2025年劳动节假期为5月1日至5月5日, 4月27日为调休工作日
+        let start = NaiveDate::from_ymd_opt(2025, 4, 25).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 5, 6).unwrap();
+        assert_eq!(BUSINESS.count_days(start, end), 7);
+        assert_eq!(SSE_BUSINESS.count_days(start, end), 4);
+        assert_eq!(IB_BUSINESS.count_days(start, end), 5);
+        assert_eq!(IB_BUSINESS.count_days(end, start), -5);
+    }
 }
@@ -0,0 +1,144 @@
+use super::Future;
+use crate::bond::{Bond, Market};
+use anyhow::Result;
+use chrono::NaiveDate;
+use std::borrow::Borrow;
+use std::sync::Arc;
+
+impl Future {
+    /// 判断债券是否属于期货合约的可交割券篮子
+    ///
+    /// 要求为指定市场的记账式附息国债, 在合约最后交易日前已起息, 且满足合约的剩余期限和发行期限要求
+    pub fn is_basket_member(
+        &self,
+        bond: &Bond,
+        mkt: Market,
+        last_trading_date: NaiveDate,
+        delivery_date: NaiveDate,
+    ) -> Result<bool> {
+        Ok(bond.mkt == mkt
+            && bond.is_treasury()
+            && bond.carry_date <= last_trading_date
+            && self.is_deliverable(bond.carry_date, bond.maturity_date, Some(delivery_date))?)
+    }
+
+    /// 从给定债券中筛选期货合约的可交割券篮子, 按债券代码排序
+    ///
+    /// 同一只国债在银行间和交易所有不同代码, 通过mkt指定只保留一个市场的代码
+    pub fn filter_basket<B: Borrow<Bond>>(
+        &self,
+        bonds: impl IntoIterator<Item = B>,
+        mkt: Market,
+    ) -> Result<Vec<B>> {
+        let last_trading_date = self.last_trading_date()?;
+        let delivery_date = self.deliver_date()?;
+        let mut basket = Vec::new();
+        for bond in bonds {
+            if self.is_basket_member(bond.borrow(), mkt, last_trading_date, delivery_date)? {
+                basket.push(bond);
+            }
+        }
+        basket.sort_by(|a, b| a.borrow().bond_code.cmp(&b.borrow().bond_code));
+        Ok(basket)
+    }
+
+    /// 扫描本地债券映射文件, 获取期货合约的可交割券篮子
+    #[inline]
+    pub fn deliverable_basket(&self, mkt: Market) -> Result<Vec<Arc<Bond>>> {
+        self.filter_basket(Bond::read_disk_all(), mkt)
+    }
+
+    /// 扫描duckdb债券信息表(默认为bond_info), 获取期货合约的可交割券篮子
+    #[cfg(feature = "duckdb")]
+    pub fn deliverable_basket_duckdb(
+        &self,
+        con: &duckdb::Connection,
+        table_name: Option<&str>,
+        mkt: Market,
+    ) -> Result<Vec<Bond>> {
+        self.filter_basket(Bond::read_duckdb_all(con, table_name, "%国债%")?, mkt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_basket() {
+        let bond = |code: &str, abbr: &str, carry: (i32, u32, u32), maturity: (i32, u32, u32)| {
+            Arc::new(Bond {
+                bond_code: code.into(),
+                abbr: abbr.into(),
+                carry_date: NaiveDate::from_ymd_opt(carry.0, carry.1, carry.2).unwrap(),
+                maturity_date: NaiveDate::from_ymd_opt(maturity.0, maturity.1, maturity.2).unwrap(),
+                ..Default::default()
+            })
+        };
+        let bonds = vec![
+            bond("250011.IB", "25附息国债11", (2025, 5, 25), (2035, 5, 25)),
+            bond("240011.IB", "24附息国债11", (2024, 5, 25), (2034, 5, 25)),
+            // 剩余期限不足6.5年
+            bond("230008.IB", "23附息国债08", (2023, 4, 15), (2030, 4, 15)),
+            // 发行期限超过10年
+            bond("2400001.IB", "24特别国债01", (2024, 5, 17), (2054, 5, 17)),
+            // 非国债
+            bond("250210.IB", "25国开10", (2025, 5, 25), (2035, 5, 25)),
+            // 合约最后交易日之后起息
+            bond("250025.IB", "25附息国债25", (2025, 12, 25), (2035, 12, 25)),
+        ];
+        let basket = Future::new("T2512")
+            .filter_basket(bonds.clone(), Market::IB)
+            .unwrap();
+        let codes: Vec<_> = basket.iter().map(|b| b.code()).collect();
+        assert_eq!(codes, vec!["240011", "250011"]);
+        let basket = Future::new("TL2512")
+            .filter_basket(bonds.clone(), Market::IB)
+            .unwrap();
+        assert_eq!(basket.len(), 1);
+        assert_eq!(basket[0].code(), "2400001");
+        assert!(
+            Future::new("T2512")
+                .filter_basket(bonds.clone(), Market::SH)
+                .unwrap()
+                .is_empty()
+        );
+        // 也可以直接筛选Bond
+        let owned: Vec<Bond> = bonds.iter().map(|b| Bond::clone(b)).collect();
+        let basket = Future::new("T2512")
+            .filter_basket(owned, Market::IB)
+            .unwrap();
+        assert_eq!(basket.len(), 2);
+    }
+
+    #[cfg(feature = "duckdb")]
+    #[test]
+    fn test_deliverable_basket_duckdb() {
+        let con = duckdb::Connection::open_in_memory().unwrap();
+        con.execute_batch(
+            "create table bond_info (
+                s_info_windcode varchar, s_info_name varchar, b_info_par double,
+                b_info_coupon integer, b_info_interesttype double, b_info_couponrate double,
+                b_info_spread double, b_info_interestfrequency varchar, b_info_carrydate varchar,
+                b_info_maturitydate varchar, b_tendrst_referyield double, b_info_issueprice double
+            );
+            insert into bond_info values
+                ('250011.IB', '25附息国债11', 100, 505001000, 501002000, 1.67, null, 'M6', '20250525', '20350525', null, null),
+                ('240011.IB', '24附息国债11', 100, 505001000, 501002000, 2.27, null, 'M6', '20240525', '20340525', null, null),
+                ('230008.IB', '23附息国债08', 100, 505001000, 501002000, 2.62, null, 'M6', '20230415', '20300415', null, null),
+                ('250011.SH', '25附息国债11', 100, 505001000, 501002000, 1.67, null, 'M6', '20250525', '20350525', null, null),
+                ('250210.IB', '25国开10', 100, 505001000, 501002000, 1.8, null, 'Y1', '20250525', '20350525', null, null);",
+        )
+        .unwrap();
+        let basket = Future::new("T2512")
+            .deliverable_basket_duckdb(&con, None, Market::IB)
+            .unwrap();
+        let codes: Vec<_> = basket.iter().map(|b| b.bond_code.as_str()).collect();
+        assert_eq!(codes, vec!["240011.IB", "250011.IB"]);
+        assert!(
+            Future::new("T2512")
+                .deliverable_basket_duckdb(&con, Some("bond_info;"), Market::IB)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use super::{Future, FutureType};
+use crate::SmallStr;
+use anyhow::{Result, bail};
+use chrono::NaiveDate;
+use std::fmt;
+use std::str::FromStr;
+
+/// 中金所的标准市场后缀, CFFEX/CCFX等其他写法会统一为该后缀
+pub const CFFEX_MARKET: &str = "CFE";
+
+/// 期货代码解析错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FutureCodeError {
+    Empty,                 // 代码为空
+    UnknownType(String),   // 品种前缀未登记
+    InvalidDigits(String), // 数字部分既不是合约年月也不是连续合约序号
+    InvalidMonth(String),  // 合约月份不是季月
+    UnknownMarket(String), // 无法识别的市场后缀
+    NotSpecific(String),   // 连续合约代码没有对应的合约月份
+}
+
+impl fmt::Display for FutureCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Future code is empty"),
+            Self::UnknownType(code) => write!(f, "Unknown future type of code: {code}"),
+            Self::InvalidDigits(code) => write!(f, "Invalid future code: {code}"),
+            Self::InvalidMonth(code) => write!(f, "Invalid future month: {code}"),
+            Self::UnknownMarket(code) => write!(f, "Unknown future market of code: {code}"),
+            Self::NotSpecific(code) => {
+                write!(
+                    f,
+                    "Continuous future code {code} does not refer to a contract"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FutureCodeError {}
+
+/// 统一市场后缀的写法, 无法识别时返回None
+#[inline]
+fn canonical_market(market: &str) -> Option<&'static str> {
+    match market {
+        "CFE" | "CFFEX" | "CCFX" | "CFX" => Some(CFFEX_MARKET),
+        _ => None,
+    }
+}
+
+/// 统一大小写并拆分合约代码和市场后缀, 不做校验
+pub(super) fn normalize(code: &str) -> (SmallStr, Option<SmallStr>) {
+    let code = code.trim().to_ascii_uppercase();
+    match code.split_once('.') {
+        Some((code, market)) => (
+            code.into(),
+            Some(canonical_market(market).unwrap_or(market).into()),
+        ),
+        None => (code.into(), None),
+    }
+}
+
+/// 拆分品种前缀和数字部分
+#[inline]
+fn split_digits(code: &str) -> (&str, &str) {
+    let idx = code
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(code.len());
+    code.split_at(idx)
+}
+
+/// 合约代码对应的已登记品种, 品种前缀为代码中首个数字之前的部分
+#[inline]
+fn code_type(code: &str) -> Option<FutureType> {
+    FutureType::from_str(split_digits(code).0).ok()
+}
+
+/// 解析具体合约代码, 返回(品种前缀, 两位年份, 月份)
+pub(super) fn parse_code(code: &str) -> Result<(&str, u32, u32), FutureCodeError> {
+    let (prefix, digits) = split_digits(code);
+    if prefix.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(FutureCodeError::InvalidDigits(code.to_string()));
+    }
+    match digits.len() {
+        0 | 2 => Err(FutureCodeError::NotSpecific(code.to_string())),
+        4 => {
+            let yymm: u32 = digits.parse().unwrap();
+            let month = yymm % 100;
+            if !(1..=12).contains(&month) {
+                return Err(FutureCodeError::InvalidMonth(code.to_string()));
+            }
+            Ok((prefix, yymm / 100, month))
+        }
+        _ => Err(FutureCodeError::InvalidDigits(code.to_string())),
+    }
+}
+
+impl Future {
+    /// 严格解析期货代码并规范化
+    ///
+    /// 支持"T2412"、"T2412.CFE"、"T2412.CFFEX"等具体合约代码, "T"、"T.CFE"主力连续代码,
+    /// 以及"T00"、"T01"、"T02"当季、下季、隔季连续代码, 不区分大小写;
+    /// 市场后缀统一为CFE, 具体合约的月份需为季月
+    pub fn parse(code: &str) -> Result<Self, FutureCodeError> {
+        let raw = code;
+        let (code, market) = normalize(code);
+        if code.is_empty() {
+            return Err(FutureCodeError::Empty);
+        }
+        if market.as_deref().is_some_and(|m| m != CFFEX_MARKET) {
+            return Err(FutureCodeError::UnknownMarket(raw.to_string()));
+        }
+        if code_type(&code).is_none() {
+            return Err(FutureCodeError::UnknownType(raw.to_string()));
+        }
+        let (_, digits) = split_digits(&code);
+        match digits.len() {
+            0 => {}
+            2 if matches!(digits, "00" | "01" | "02") => {}
+            _ => {
+                let (_, _, month) = parse_code(&code).map_err(|e| match e {
+                    FutureCodeError::InvalidMonth(_) => {
+                        FutureCodeError::InvalidMonth(raw.to_string())
+                    }
+                    _ => FutureCodeError::InvalidDigits(raw.to_string()),
+                })?;
+                if month % 3 != 0 {
+                    return Err(FutureCodeError::InvalidMonth(raw.to_string()));
+                }
+            }
+        }
+        Ok(Self { code, market })
+    }
+
+    /// 期货品种, 与`Future::parse`按相同规则识别品种前缀, 前缀未登记时返回`FutureCodeError::UnknownType`
+    #[inline]
+    pub fn future_type(&self) -> Result<FutureType, FutureCodeError> {
+        code_type(&self.code).ok_or_else(|| FutureCodeError::UnknownType(self.code.to_string()))
+    }
+
+    /// 是否为连续合约代码
+    #[inline]
+    pub fn is_continuous(&self) -> bool {
+        matches!(split_digits(&self.code).1.len(), 0 | 2)
+    }
+
+    /// 将当季、下季、隔季连续代码解析为date当日对应的具体合约, 具体合约代码返回自身
+    ///
+    /// 主力连续合约需要根据持仓量判断, 参见ContinuousBuilder
+    pub fn resolve(&self, date: NaiveDate) -> Result<Self> {
+        let (_, digits) = split_digits(&self.code);
+        let n: usize = match digits.len() {
+            0 => bail!(
+                "Main continuous future {} can not be resolved without open interest",
+                self.code
+            ),
+            2 => digits.parse()?,
+            _ => return Ok(self.clone()),
+        };
+        let futures = Future::trading_futures(date, None, Some(self.future_type()?))?;
+        match futures.into_iter().nth(n) {
+            Some(future) => Ok(Self {
+                market: self.market.clone(),
+                ..future
+            }),
+            None => bail!("No trading future for {} on {}", self.code, date),
+        }
+    }
+}
+
+impl FromStr for Future {
+    type Err = FutureCodeError;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_future_code() {
+        let expect = Future {
+            code: "T2412".into(),
+            market: Some("CFE".into()),
+        };
+        for code in ["T2412.CFE", "T2412.CFFEX", "t2412.cfe", " T2412.ccfx "] {
+            assert_eq!(Future::parse(code).unwrap(), expect);
+            assert_eq!(Future::new(code), expect);
+        }
+        assert_eq!(
+            "tf2503".parse::<Future>().unwrap(),
+            Future {
+                code: "TF2503".into(),
+                market: None,
+            }
+        );
+        // 连续合约代码
+        let main = Future::parse("T.CFE").unwrap();
+        assert_eq!(main.code, "T");
+        assert!(main.is_continuous());
+        assert!(Future::parse("TL00").unwrap().is_continuous());
+        assert!(!expect.is_continuous());
+        assert!(matches!(
+            main.last_trading_date().unwrap_err().downcast_ref(),
+            Some(FutureCodeError::NotSpecific(_))
+        ));
+
+        assert_eq!(Future::new("TF2412.CFE").future_type(), Ok(FutureType::TF));
+        assert_eq!(main.future_type(), Ok(FutureType::T));
+        assert_eq!(
+            Future::new("X2412").future_type(),
+            Err(FutureCodeError::UnknownType("X2412".into()))
+        );
+
+        assert_eq!(Future::parse(""), Err(FutureCodeError::Empty));
+        assert_eq!(
+            Future::parse("X2412"),
+            Err(FutureCodeError::UnknownType("X2412".into()))
+        );
+        assert_eq!(
+            Future::parse("T2412.SHF"),
+            Err(FutureCodeError::UnknownMarket("T2412.SHF".into()))
+        );
+        assert_eq!(
+            Future::parse("T2411"),
+            Err(FutureCodeError::InvalidMonth("T2411".into()))
+        );
+        assert_eq!(
+            Future::parse("T241"),
+            Err(FutureCodeError::InvalidDigits("T241".into()))
+        );
+        assert_eq!(
+            Future::parse("T05"),
+            Err(FutureCodeError::InvalidDigits("T05".into()))
+        );
+    }
+
+    #[test]
+    fn test_resolve_continuous() {
+        let date = NaiveDate::from_ymd_opt(2025, 9, 15).unwrap();
+        let resolve = |code: &str| Future::parse(code).unwrap().resolve(date).unwrap();
+        assert_eq!(resolve("T00").code, "T2512");
+        assert_eq!(resolve("T01.CFE").code, "T2603");
+        assert_eq!(resolve("T01.CFE").market.as_deref(), Some("CFE"));
+        assert_eq!(resolve("T02").code, "T2606");
+        assert_eq!(resolve("T2509").code, "T2509");
+        assert!(Future::parse("T").unwrap().resolve(date).is_err());
+    }
+}
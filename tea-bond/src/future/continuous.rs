@@ -0,0 +1,341 @@
+use super::Future;
+use anyhow::{Result, bail, ensure};
+use chrono::NaiveDate;
+use std::collections::{BTreeSet, HashMap};
+use std::str::FromStr;
+use tea_calendar::{Calendar, china::CFFEX};
+
+/// 单个合约的单日行情
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ContractBar {
+    pub date: NaiveDate,
+    pub price: f64,         // 价格, 通常为收盘价或结算价
+    pub open_interest: f64, // 持仓量
+    pub volume: f64,        // 成交量
+}
+
+/// 主力合约换月规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollRule {
+    // 更远月合约持仓量超过当前主力合约时换月
+    OpenInterest,
+    // 更远月合约成交量超过当前主力合约时换月
+    Volume,
+    // 在当前主力合约最后交易日之前的第n个交易日换月
+    DaysBeforeExpiry(i32),
+}
+
+/// 连续合约价格的复权方式
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AdjustMode {
+    // 不复权, 直接拼接各主力合约价格
+    #[default]
+    None,
+    // 等比复权, 换月前的价格乘以新旧合约价格之比
+    Ratio,
+    // 差值复权, 换月前的价格加上新旧合约价格之差
+    Difference,
+}
+
+impl FromStr for AdjustMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "None" | "none" => Ok(AdjustMode::None),
+            "Ratio" | "ratio" => Ok(AdjustMode::Ratio),
+            "Difference" | "difference" | "diff" => Ok(AdjustMode::Difference),
+            _ => bail!("Unknown adjust mode: {}", s),
+        }
+    }
+}
+
+/// 一次主力合约换月
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollEvent {
+    pub date: NaiveDate,        // 首个使用新主力合约的日期
+    pub signal_date: NaiveDate, // 触发换月的日期, 复权使用该日的新旧合约价格
+    pub from: Future,
+    pub to: Future,
+    pub from_price: f64,
+    pub to_price: f64,
+}
+
+/// 主力连续合约序列
+#[derive(Debug, Clone, Default)]
+pub struct ContinuousSeries {
+    pub dates: Vec<NaiveDate>,
+    pub contracts: Vec<Future>, // 每日的主力合约
+    pub raw_prices: Vec<f64>,   // 主力合约的原始价格
+    pub prices: Vec<f64>,       // 复权后的价格, 最新主力合约的价格保持不变
+    pub rolls: Vec<RollEvent>,
+}
+
+/// 主力连续合约构建器
+#[derive(Debug, Clone, Copy)]
+pub struct ContinuousBuilder {
+    pub rule: RollRule,
+    pub adjust: AdjustMode,
+}
+
+/// 按最后交易日排序的合约及其行情
+struct ContractQuotes {
+    future: Future,
+    last_trading_date: NaiveDate,
+    bars: HashMap<NaiveDate, ContractBar>,
+}
+
+impl ContinuousBuilder {
+    #[inline]
+    pub fn new(rule: RollRule, adjust: AdjustMode) -> Self {
+        Self { rule, adjust }
+    }
+
+    /// 换月指标, 指标越大越倾向于成为主力合约
+    #[inline]
+    fn score(&self, bar: &ContractBar) -> f64 {
+        match self.rule {
+            RollRule::Volume => bar.volume,
+            _ => bar.open_interest,
+        }
+    }
+
+    /// 当前主力合约在date收盘后是否应该换月, 返回新的主力合约
+    fn roll_signal(
+        &self,
+        contracts: &[ContractQuotes],
+        current: usize,
+        date: NaiveDate,
+    ) -> Option<usize> {
+        let bar = contracts[current].bars.get(&date)?;
+        let mut later = (current + 1..contracts.len())
+            .filter(|i| contracts[*i].last_trading_date > contracts[current].last_trading_date)
+            .filter_map(|i| contracts[i].bars.get(&date).map(|b| (i, b)));
+        match self.rule {
+            RollRule::DaysBeforeExpiry(n) => {
+                let roll_date = CFFEX.find_workday(contracts[current].last_trading_date, -n);
+                if CFFEX.find_workday(date, 1) >= roll_date {
+                    later.next().map(|(i, _)| i)
+                } else {
+                    None
+                }
+            }
+            _ => later
+                .filter(|(_, b)| self.score(b) > self.score(bar))
+                .max_by(|(_, a), (_, b)| self.score(a).total_cmp(&self.score(b)))
+                .map(|(i, _)| i),
+        }
+    }
+
+    /// 没有主力合约或主力合约在date没有行情时, 从after之后的合约中选取主力合约
+    fn select(
+        &self,
+        contracts: &[ContractQuotes],
+        after: Option<usize>,
+        date: NaiveDate,
+    ) -> Option<usize> {
+        let mut candidates = (after.map_or(0, |i| i + 1)..contracts.len())
+            .filter(|i| contracts[*i].last_trading_date >= date)
+            .filter_map(|i| contracts[i].bars.get(&date).map(|b| (i, b)));
+        match self.rule {
+            RollRule::DaysBeforeExpiry(n) => candidates
+                .find(|(i, _)| date < CFFEX.find_workday(contracts[*i].last_trading_date, -n))
+                .map(|(i, _)| i),
+            _ => candidates
+                .max_by(|(_, a), (_, b)| self.score(a).total_cmp(&self.score(b)))
+                .map(|(i, _)| i),
+        }
+    }
+
+    /// 由各合约的行情构建主力连续合约序列
+    ///
+    /// 换月信号在当日收盘后确定, 次一交易日起使用新的主力合约, 主力合约只会向更远月切换;
+    /// 若主力合约在某日没有行情(如已到期), 则当日直接切换至有行情的更远月合约
+    pub fn build(
+        &self,
+        contracts: impl IntoIterator<Item = (Future, Vec<ContractBar>)>,
+    ) -> Result<ContinuousSeries> {
+        if let RollRule::DaysBeforeExpiry(n) = self.rule {
+            ensure!(n >= 0, "Roll days should be non-negative, got {}", n);
+        }
+        let mut dates = BTreeSet::new();
+        let mut quotes = Vec::new();
+        for (future, bars) in contracts {
+            let last_trading_date = future.last_trading_date()?;
+            let bars: HashMap<_, _> = bars
+                .into_iter()
+                .filter(|b| b.price.is_finite())
+                .map(|b| (b.date, b))
+                .collect();
+            dates.extend(bars.keys().copied());
+            quotes.push(ContractQuotes {
+                future,
+                last_trading_date,
+                bars,
+            });
+        }
+        quotes.sort_by_key(|q| q.last_trading_date);
+
+        let mut out = ContinuousSeries::default();
+        let mut roll_idx = Vec::new(); // 每次换月在输出序列中的位置
+        let mut current: Option<usize> = None; // 上一交易日的主力合约
+        let mut pending: Option<(usize, NaiveDate)> = None; // 待切换的主力合约及换月信号日
+        for date in dates {
+            let (target, signal_date) = pending.map_or((current, date), |(i, d)| (Some(i), d));
+            // 主力合约当日没有行情时直接换月
+            let cur = match target {
+                Some(i) if quotes[i].bars.contains_key(&date) => i,
+                _ => match self.select(&quotes, target, date) {
+                    Some(i) => i,
+                    None => continue,
+                },
+            };
+            if let Some(from) = current.filter(|from| *from != cur) {
+                // 换月信号日的旧合约价格, 缺失时使用旧合约最近一次价格
+                let from_price = quotes[from]
+                    .bars
+                    .get(&signal_date)
+                    .or_else(|| {
+                        quotes[from]
+                            .bars
+                            .values()
+                            .filter(|b| b.date <= signal_date)
+                            .max_by_key(|b| b.date)
+                    })
+                    .map_or(f64::NAN, |b| b.price);
+                let to_price = quotes[cur]
+                    .bars
+                    .get(&signal_date)
+                    .unwrap_or(&quotes[cur].bars[&date])
+                    .price;
+                roll_idx.push(out.dates.len());
+                out.rolls.push(RollEvent {
+                    date,
+                    signal_date,
+                    from: quotes[from].future.clone(),
+                    to: quotes[cur].future.clone(),
+                    from_price,
+                    to_price,
+                });
+            }
+            current = Some(cur);
+            out.dates.push(date);
+            out.contracts.push(quotes[cur].future.clone());
+            out.raw_prices.push(quotes[cur].bars[&date].price);
+            pending = self.roll_signal(&quotes, cur, date).map(|i| (i, date));
+        }
+        out.prices = self.back_adjust(&out.raw_prices, &roll_idx, &out.rolls);
+        Ok(out)
+    }
+
+    /// 从最新的主力合约向前逐次复权, 每次换月调整其之前的全部价格
+    fn back_adjust(&self, raw_prices: &[f64], roll_idx: &[usize], rolls: &[RollEvent]) -> Vec<f64> {
+        let mut prices = raw_prices.to_vec();
+        for (idx, roll) in roll_idx.iter().zip(rolls).rev() {
+            let adjusted = &mut prices[..*idx];
+            match self.adjust {
+                AdjustMode::None => break,
+                AdjustMode::Ratio => {
+                    let ratio = roll.to_price / roll.from_price;
+                    adjusted.iter_mut().for_each(|p| *p *= ratio);
+                }
+                AdjustMode::Difference => {
+                    let diff = roll.to_price - roll.from_price;
+                    adjusted.iter_mut().for_each(|p| *p += diff);
+                }
+            }
+        }
+        prices
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_contracts(near_oi: &[f64], far_oi: &[f64]) -> Vec<(Future, Vec<ContractBar>)> {
+        let dates: Vec<NaiveDate> = (0..15)
+            .map(|i| NaiveDate::from_ymd_opt(2024, 2, 26).unwrap() + chrono::Duration::days(i))
+            .filter(|d| CFFEX.is_business_day(*d))
+            .collect();
+        let bars = |oi: &[f64], price: f64| -> Vec<ContractBar> {
+            dates
+                .iter()
+                .zip(oi)
+                .map(|(date, oi)| ContractBar {
+                    date: *date,
+                    price,
+                    open_interest: *oi,
+                    volume: *oi * 2.,
+                })
+                .collect()
+        };
+        vec![
+            (Future::new("T2406"), bars(far_oi, 101.)),
+            (Future::new("T2403"), bars(near_oi, 100.)),
+        ]
+    }
+
+    #[test]
+    fn test_continuous_builder() {
+        let near_oi = [100., 90., 80., 60., 40., 30., 20., 10., 5., 1.];
+        let far_oi = [10., 30., 50., 70., 90., 100., 110., 120., 130., 140., 150.];
+        let contracts = get_contracts(&near_oi, &far_oi);
+        let series = ContinuousBuilder::new(RollRule::OpenInterest, AdjustMode::Ratio)
+            .build(contracts.clone())
+            .unwrap();
+        assert_eq!(series.dates.len(), 11);
+        assert_eq!(series.rolls.len(), 1);
+        let roll = &series.rolls[0];
+        // 2月29日远月持仓量超过近月, 次一交易日换月
+        assert_eq!(
+            roll.signal_date,
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(roll.date, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        assert_eq!(
+            (roll.from.code.as_str(), roll.to.code.as_str()),
+            ("T2403", "T2406")
+        );
+        assert_eq!(series.contracts[3].code, "T2403");
+        assert_eq!(series.contracts[4].code, "T2406");
+        assert_eq!(series.raw_prices[3], 100.);
+        assert!(series.prices.iter().all(|p| (p - 101.).abs() < 1e-10));
+        let series = ContinuousBuilder::new(RollRule::Volume, AdjustMode::Difference)
+            .build(contracts.clone())
+            .unwrap();
+        assert_eq!(series.rolls[0].date, roll.date);
+        assert!(series.prices.iter().all(|p| (p - 101.).abs() < 1e-10));
+
+        // 最后交易日(3月8日)前2个交易日换月
+        let series = ContinuousBuilder::new(RollRule::DaysBeforeExpiry(2), AdjustMode::None)
+            .build(contracts)
+            .unwrap();
+        assert_eq!(series.rolls.len(), 1);
+        assert_eq!(
+            series.rolls[0].date,
+            NaiveDate::from_ymd_opt(2024, 3, 6).unwrap()
+        );
+        assert_eq!(series.prices, series.raw_prices);
+
+        // 远月持仓量始终低于近月时, 近月到期后强制换月
+        let far_oi = [1.; 11];
+        let series = ContinuousBuilder::new(RollRule::OpenInterest, AdjustMode::Difference)
+            .build(get_contracts(&near_oi, &far_oi))
+            .unwrap();
+        let roll = &series.rolls[0];
+        assert_eq!(roll.date, NaiveDate::from_ymd_opt(2024, 3, 11).unwrap());
+        assert_eq!(roll.signal_date, roll.date);
+        assert_eq!((roll.from_price, roll.to_price), (100., 101.));
+        assert_eq!(series.prices[0], 101.);
+        assert!(
+            ContinuousBuilder::new(RollRule::DaysBeforeExpiry(-1), AdjustMode::None)
+                .build(Vec::new())
+                .is_err()
+        );
+        assert_eq!(
+            "diff".parse::<AdjustMode>().unwrap(),
+            AdjustMode::Difference
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use super::{CFFEX_DEFAULT_CP_RATE, Future, FutureType};
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// 国债期货合约规格
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContractSpec {
+    pub contract_size: f64,           // 合约面值(元)
+    pub tick_size: f64,               // 最小变动价位
+    pub price_limit: f64,             // 每日价格最大波动限制, 相对上一交易日结算价的比例
+    pub margin_ratio: f64,            // 最低交易保证金比例
+    pub fictitious_cp_rate: f64,      // 虚拟券票面利率, 用于计算转换因子
+    pub max_issue_year: i32,          // 可交割券发行期限上限(年)
+    pub min_remain_year: f64,         // 可交割券在合约到期月份首日的剩余期限下限(年)
+    pub max_remain_year: Option<f64>, // 可交割券在合约到期月份首日的剩余期限上限(年)
+}
+
+#[inline]
+fn remain_year(delivery_date: NaiveDate, maturity_date: NaiveDate) -> f64 {
+    let year_diff = maturity_date.year() - delivery_date.year();
+    let month_diff = maturity_date.month() as i32 - delivery_date.month() as i32;
+    let date_diff = maturity_date.day() as i32 - 1; // 规定为与期货到期月首日的差值
+    year_diff as f64 + month_diff as f64 / 12.0 + date_diff as f64 / 365.0
+}
+
+impl ContractSpec {
+    /// 中金所现行的合约规格, 非中金所现有品种返回None
+    pub fn cffex(typ: FutureType) -> Option<Self> {
+        let spec = match typ {
+            // 2年期国债期货
+            // 发行期限不高于5年，合约到期月份首日剩余期限为1.5-2.25年的记账式附息国债
+            FutureType::TS => Self {
+                contract_size: 2_000_000.,
+                tick_size: 0.002,
+                price_limit: 0.005,
+                margin_ratio: 0.005,
+                fictitious_cp_rate: CFFEX_DEFAULT_CP_RATE,
+                max_issue_year: 5,
+                min_remain_year: 1.5,
+                max_remain_year: Some(2.25),
+            },
+            // 5年期国债期货
+            // 发行期限不高于7年、合约到期月份首日剩余期限为4-5.25年的记账式附息国债
+            FutureType::TF => Self {
+                contract_size: 1_000_000.,
+                tick_size: 0.005,
+                price_limit: 0.012,
+                margin_ratio: 0.01,
+                fictitious_cp_rate: CFFEX_DEFAULT_CP_RATE,
+                max_issue_year: 7,
+                min_remain_year: 4.,
+                max_remain_year: Some(5.25),
+            },
+            // 10年期国债期货
+            // 发行期限不高于10年、合约到期月份首日剩余期限不低于6.5年的记账式附息国债
+            FutureType::T => Self {
+                contract_size: 1_000_000.,
+                tick_size: 0.005,
+                price_limit: 0.02,
+                margin_ratio: 0.02,
+                fictitious_cp_rate: CFFEX_DEFAULT_CP_RATE,
+                max_issue_year: 10,
+                min_remain_year: 6.5,
+                max_remain_year: None,
+            },
+            // 30年期国债期货
+            // 发行期限不高于30年，合约到期月份首日剩余期限不低于25年的记账式附息国债
+            FutureType::TL => Self {
+                contract_size: 1_000_000.,
+                tick_size: 0.01,
+                price_limit: 0.035,
+                margin_ratio: 0.035,
+                fictitious_cp_rate: CFFEX_DEFAULT_CP_RATE,
+                max_issue_year: 30,
+                min_remain_year: 25.,
+                max_remain_year: None,
+            },
+            _ => return None,
+        };
+        Some(spec)
+    }
+
+    /// 合约乘数, 期货价格按百元面值报价, 合约乘数 = 合约面值 / 100
+    #[inline]
+    pub fn multiplier(&self) -> f64 {
+        self.contract_size / 100.
+    }
+
+    /// 最小变动价位对应的每手合约价值变动(元)
+    #[inline]
+    pub fn tick_value(&self) -> f64 {
+        self.tick_size * self.multiplier()
+    }
+
+    /// 判断债券是否满足可交割券的期限要求
+    pub fn is_deliverable(
+        &self,
+        delivery_date: NaiveDate,
+        carry_date: NaiveDate,
+        maturity_date: NaiveDate,
+    ) -> bool {
+        let issue_year = maturity_date.year() - carry_date.year();
+        let remain_year = remain_year(delivery_date, maturity_date);
+        issue_year <= self.max_issue_year
+            && remain_year >= self.min_remain_year
+            && self.max_remain_year.is_none_or(|max| remain_year <= max)
+    }
+}
+
+impl FutureType {
+    /// 最后交易日为date的合约适用的合约规格, 按品种登记的规则选取
+    #[inline]
+    pub fn contract_spec(&self, date: NaiveDate) -> ContractSpec {
+        self.with_product(|product| product.rule(date).spec)
+    }
+}
+
+impl Future {
+    /// 期货合约适用的合约规格, 按合约最后交易日选取
+    #[inline]
+    pub fn contract_spec(&self) -> Result<ContractSpec> {
+        Ok(self.future_type()?.contract_spec(self.last_trading_date()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::future::{ContractRule, FutureProduct};
+
+    #[test]
+    fn test_contract_spec() {
+        let spec = Future::new("TS2412").contract_spec().unwrap();
+        assert_eq!(spec.contract_size, 2e6);
+        assert_eq!(spec.multiplier(), 20000.);
+        assert_eq!(spec.tick_value(), 40.);
+        assert_eq!(
+            Future::new("T2412").contract_spec().unwrap().multiplier(),
+            10000.
+        );
+
+        // 早期的5年期和10年期合约适用修订前的可交割券范围
+        let spec = Future::new("TF1912").contract_spec().unwrap();
+        assert_eq!((spec.min_remain_year, spec.max_remain_year), (4., Some(7.)));
+        assert_eq!(
+            Future::new("TF2003").contract_spec().unwrap(),
+            ContractSpec::cffex(FutureType::TF).unwrap()
+        );
+        assert_eq!(
+            Future::new("T1509")
+                .contract_spec()
+                .unwrap()
+                .max_remain_year,
+            Some(10.25)
+        );
+        assert_eq!(
+            Future::new("T2003")
+                .contract_spec()
+                .unwrap()
+                .max_remain_year,
+            None
+        );
+        // 剩余期限约6年的10年期国债只是修订前TF合约的可交割券
+        let carry_date = NaiveDate::from_ymd_opt(2016, 1, 14).unwrap();
+        let maturity_date = NaiveDate::from_ymd_opt(2026, 1, 14).unwrap();
+        assert!(
+            Future::new("TF1912")
+                .is_deliverable(carry_date, maturity_date, None)
+                .unwrap()
+        );
+        assert!(
+            !Future::new("TF2003")
+                .is_deliverable(carry_date, maturity_date, None)
+                .unwrap()
+        );
+
+        // 新规则只对最后交易日不早于生效日期的合约适用
+        let typ = FutureType::TL;
+        let product = FutureProduct {
+            rules: vec![
+                typ.product().rules[0],
+                ContractRule {
+                    effective_date: NaiveDate::from_ymd_opt(2030, 1, 1).unwrap(),
+                    spec: ContractSpec {
+                        fictitious_cp_rate: 0.025,
+                        min_remain_year: 20.,
+                        ..ContractSpec::cffex(typ).unwrap()
+                    },
+                },
+            ],
+            ..typ.product()
+        };
+        let last_trading_date = Future::new("TL3012").last_trading_date().unwrap();
+        let spec = product.rule(last_trading_date).spec;
+        assert_eq!(spec.fictitious_cp_rate, 0.025);
+        let delivery_date = Future::new("TL3012").deliver_date().unwrap();
+        let carry_date = NaiveDate::from_ymd_opt(2024, 5, 17).unwrap();
+        let maturity_date = NaiveDate::from_ymd_opt(2054, 5, 17).unwrap();
+        assert!(spec.is_deliverable(delivery_date, carry_date, maturity_date));
+        let last_trading_date = Future::new("TL2912").last_trading_date().unwrap();
+        assert_eq!(
+            product.rule(last_trading_date).spec,
+            ContractSpec::cffex(typ).unwrap()
+        );
+        // 登记的品种仍使用现行规则, 按缴款日判断与按合约判断一致
+        assert!(!typ.is_deliverable(delivery_date, carry_date, maturity_date));
+        assert!(
+            !Future::new("TL3012")
+                .is_deliverable(carry_date, maturity_date, None)
+                .unwrap()
+        );
+    }
+}
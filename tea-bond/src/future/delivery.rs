@@ -0,0 +1,128 @@
+use super::Future;
+use anyhow::{Result, ensure};
+use chrono::{Datelike, NaiveDate};
+use tea_calendar::{Calendar, china::CFFEX};
+
+/// 国债期货交割流程的各个日期
+///
+/// 滚动交割: 交割月首个交易日至最后交易日前一交易日, 卖方申报交割意向当日为第一交割日(配对日),
+/// 次一交易日为第二交割日(缴款日), 再次一交易日为第三交割日(交券日);
+/// 集中交割: 最后交易日闭市后申报交割意向, 其后第一、二、三个交易日分别为配对日、缴款日和交券日
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliverySchedule {
+    pub intention_date: NaiveDate, // 交割意向申报日
+    pub matching_date: NaiveDate,  // 配对日
+    pub payment_date: NaiveDate,   // 缴款日, 发票价格和持有收益按该日计算
+    pub transfer_date: NaiveDate,  // 交券日
+    pub rolling: bool,             // 是否为滚动交割
+}
+
+impl DeliverySchedule {
+    /// 以配对日为第一交割日, 依次推算缴款日和交券日
+    #[inline]
+    fn new(intention_date: NaiveDate, matching_date: NaiveDate, rolling: bool) -> Self {
+        Self {
+            intention_date,
+            matching_date,
+            payment_date: CFFEX.find_workday(matching_date, 1),
+            transfer_date: CFFEX.find_workday(matching_date, 2),
+            rolling,
+        }
+    }
+}
+
+impl Future {
+    /// 集中交割的交割流程, 其缴款日即`deliver_date`
+    pub fn final_delivery_schedule(&self) -> Result<DeliverySchedule> {
+        let last_trading_date = self.last_trading_date()?;
+        Ok(DeliverySchedule::new(
+            last_trading_date,
+            CFFEX.find_workday(last_trading_date, 1),
+            false,
+        ))
+    }
+
+    /// 交割意向申报日为intention_date时的交割流程, 为None时返回集中交割的交割流程
+    ///
+    /// 滚动交割的申报日需为交割月首个交易日至最后交易日前一交易日之间的交易日,
+    /// 申报日为最后交易日时按集中交割处理
+    pub fn delivery_schedule(&self, intention_date: Option<NaiveDate>) -> Result<DeliverySchedule> {
+        let last_trading_date = self.last_trading_date()?;
+        let intention_date = match intention_date {
+            Some(date) if date != last_trading_date => date,
+            _ => return self.final_delivery_schedule(),
+        };
+        ensure!(
+            CFFEX.is_business_day(intention_date),
+            "Delivery intention date {} is not a trading day",
+            intention_date
+        );
+        ensure!(
+            intention_date.year() == last_trading_date.year()
+                && intention_date.month() == last_trading_date.month()
+                && intention_date < last_trading_date,
+            "Delivery intention date {} of {} should be in delivery month and before {}",
+            intention_date,
+            self.code,
+            last_trading_date
+        );
+        Ok(DeliverySchedule::new(intention_date, intention_date, true))
+    }
+
+    /// 交割月内全部可申报滚动交割的交割流程, 按申报日排列
+    pub fn rolling_delivery_schedules(&self) -> Result<Vec<DeliverySchedule>> {
+        let last_trading_date = self.last_trading_date()?;
+        let mut date = last_trading_date.with_day(1).unwrap();
+        let mut schedules = Vec::new();
+        while date < last_trading_date {
+            if CFFEX.is_business_day(date) {
+                schedules.push(DeliverySchedule::new(date, date, true));
+            }
+            date = date.succ_opt().unwrap();
+        }
+        Ok(schedules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_schedule() {
+        let d = |m, d| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let future = Future::new("T2412");
+        // 最后交易日2024-12-13(周五)
+        let schedule = future.final_delivery_schedule().unwrap();
+        assert_eq!(
+            schedule,
+            DeliverySchedule {
+                intention_date: d(12, 13),
+                matching_date: d(12, 16),
+                payment_date: d(12, 17),
+                transfer_date: d(12, 18),
+                rolling: false,
+            }
+        );
+        assert_eq!(schedule.payment_date, future.deliver_date().unwrap());
+        assert_eq!(future.delivery_schedule(None).unwrap(), schedule);
+        assert_eq!(future.delivery_schedule(Some(d(12, 13))).unwrap(), schedule);
+
+        // 周四申报, 跨周末缴款和交券
+        let schedule = future.delivery_schedule(Some(d(12, 5))).unwrap();
+        assert_eq!(schedule.matching_date, d(12, 5));
+        assert_eq!(schedule.payment_date, d(12, 6));
+        assert_eq!(schedule.transfer_date, d(12, 9));
+        assert!(schedule.rolling);
+        // 非交易日、交割月之前或最后交易日之后均不能申报
+        assert!(future.delivery_schedule(Some(d(12, 7))).is_err());
+        assert!(future.delivery_schedule(Some(d(11, 29))).is_err());
+        assert!(future.delivery_schedule(Some(d(12, 16))).is_err());
+
+        let schedules = future.rolling_delivery_schedules().unwrap();
+        assert_eq!(schedules.len(), 9);
+        assert_eq!(schedules[0].intention_date, d(12, 2));
+        assert_eq!(schedules[8].intention_date, d(12, 12));
+        assert_eq!(schedules[8].transfer_date, d(12, 16));
+    }
+}
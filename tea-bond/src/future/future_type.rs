@@ -1,98 +1,108 @@
+use super::month_last_trading_date;
+use super::product::{FutureProduct, PRODUCTS};
 use anyhow::bail;
 use chrono::{Datelike, NaiveDate};
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone)]
-pub enum FutureType {
-    TS, // 2年期国债期货
-    TF, // 5年期国债期货
-    T,  // 10年期国债期货
-    TL, // 30年期国债期货
+/// 国债期货品种, 以合约代码前缀标识
+///
+/// 中金所现有品种可直接使用关联常量, 配置文件中新增的品种通过`FromStr`或`FutureType::register`获取
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FutureType(&'static str);
+
+impl FutureType {
+    pub const TS: Self = Self("TS"); // 2年期国债期货
+    pub const TF: Self = Self("TF"); // 5年期国债期货
+    pub const T: Self = Self("T"); // 10年期国债期货
+    pub const TL: Self = Self("TL"); // 30年期国债期货
+}
+
+impl fmt::Debug for FutureType {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Display for FutureType {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
 }
 
 impl FromStr for FutureType {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "T" => Ok(FutureType::T),
-            "TF" => Ok(FutureType::TF),
-            "TS" => Ok(FutureType::TS),
-            "TL" => Ok(FutureType::TL),
-            _ => bail!("Invalid future type: {}", s),
+        match PRODUCTS.read().iter().find(|(typ, _)| typ.0 == s) {
+            Some((typ, _)) => Ok(*typ),
+            None => bail!("Invalid future type: {}", s),
         }
     }
 }
 
-#[inline]
-fn remain_year(delivery_date: NaiveDate, maturity_date: NaiveDate) -> f64 {
-    let year_diff = maturity_date.year() - delivery_date.year();
-    let month_diff = maturity_date.month() as i32 - delivery_date.month() as i32;
-    let date_diff = maturity_date.day() as i32 - 1; // 规定为与期货到期月首日的差值
-    year_diff as f64 + month_diff as f64 / 12.0 + date_diff as f64 / 365.0
-}
-
 impl FutureType {
     #[inline]
-    pub(crate) const fn prefix(&self) -> &'static str {
-        match self {
-            FutureType::TS => "TS",
-            FutureType::TF => "TF",
-            FutureType::T => "T",
-            FutureType::TL => "TL",
-        }
+    pub(super) const fn from_static(prefix: &'static str) -> Self {
+        Self(prefix)
     }
 
     #[inline]
-    pub(crate) const fn listing_start_date(&self) -> NaiveDate {
-        match self {
-            // 2年
-            FutureType::TS => NaiveDate::from_ymd_opt(2018, 8, 17).unwrap(),
-            // 5年
-            FutureType::TF => NaiveDate::from_ymd_opt(2013, 9, 6).unwrap(),
-            // 10年
-            FutureType::T => NaiveDate::from_ymd_opt(2015, 3, 20).unwrap(),
-            // 30年
-            FutureType::TL => NaiveDate::from_ymd_opt(2023, 4, 21).unwrap(),
-        }
+    pub const fn prefix(&self) -> &'static str {
+        self.0
     }
 
+    /// 已登记的全部期货品种, 按登记顺序排列
+    pub fn all() -> Vec<Self> {
+        PRODUCTS.read().iter().map(|(typ, _)| *typ).collect()
+    }
+
+    /// 在读锁内访问品种定义, 只取出需要的字段, 避免复制整个品种定义
+    pub(super) fn with_product<R>(&self, f: impl FnOnce(&FutureProduct) -> R) -> R {
+        let products = PRODUCTS.read();
+        let (_, product) = products
+            .iter()
+            .find(|(typ, _)| typ == self)
+            .unwrap_or_else(|| panic!("Future type {} is not registered", self.0));
+        f(product)
+    }
+
+    /// 期货品种的定义
     #[inline]
-    pub(crate) const fn first_contracts(&self) -> [&'static str; 3] {
-        match self {
-            // 2年期首批：2018/12、2019/3、2019/6
-            FutureType::TS => ["TS1812", "TS1903", "TS1906"],
-            // 5年期首批：2013/12、2014/3、2014/6
-            FutureType::TF => ["TF1312", "TF1403", "TF1406"],
-            // 10年期首批：2015/9、2015/12、2016/3
-            FutureType::T => ["T1509", "T1512", "T1603"],
-            // 30年期首批：2023/6、2023/9、2023/12
-            FutureType::TL => ["TL2306", "TL2309", "TL2312"],
-        }
+    pub fn product(&self) -> FutureProduct {
+        self.with_product(FutureProduct::clone)
+    }
+
+    #[inline]
+    pub(crate) fn listing_start_date(&self) -> NaiveDate {
+        self.with_product(|product| product.listing_date)
+    }
+
+    /// 首批合约中最早到期的合约代码
+    #[inline]
+    pub(crate) fn first_contract(&self) -> String {
+        self.with_product(|product| product.first_contract.to_string())
     }
 
     /// 判断是不是可交割券
+    ///
+    /// 与`Future::contract_spec`一致, 按缴款日所在月份合约的最后交易日选取合约规格,
+    /// 再判断发行期限和剩余期限是否满足要求
+    #[inline]
     pub fn is_deliverable(
         &self,
         delivery_date: NaiveDate,
         carry_date: NaiveDate,
         maturity_date: NaiveDate,
     ) -> bool {
-        let issue_year = maturity_date.year() - carry_date.year();
-        let remain_year = remain_year(delivery_date, maturity_date);
-        match self {
-            // 2年期国债期货
-            // 发行期限不高于5年，合约到期月份首日剩余期限为1.5-2.25年的记账式附息国债
-            FutureType::TS => issue_year <= 5 && (1.5..=2.25).contains(&remain_year),
-            // 5年期国债期货
-            // 发行期限不高于7年、合约到期月份首日剩余期限为4-5.25年的记账式附息国债
-            FutureType::TF => issue_year <= 7 && (4.0..=5.25).contains(&remain_year),
-            // 10年期国债期货
-            // 发行期限不高于10年、合约到期月份首日剩余期限不低于6.5年的记账式附息国债
-            FutureType::T => issue_year <= 10 && remain_year >= 6.5,
-            // 30年期国债期货
-            // 发行期限不高于30年，合约到期月份首日剩余期限不低于25年的记账式附息国债
-            FutureType::TL => issue_year <= 30 && remain_year >= 25.0,
-        }
+        let last_trading_date =
+            month_last_trading_date(delivery_date.year(), delivery_date.month());
+        self.contract_spec(last_trading_date).is_deliverable(
+            delivery_date,
+            carry_date,
+            maturity_date,
+        )
     }
 }
@@ -1,13 +1,27 @@
+mod basket;
+mod code;
+mod continuous;
+mod contract_spec;
+mod delivery;
 mod future_price;
 mod future_type;
 mod impls;
-
+mod product;
+
+pub use code::{CFFEX_MARKET, FutureCodeError};
+pub use continuous::{
+    AdjustMode, ContinuousBuilder, ContinuousSeries, ContractBar, RollEvent, RollRule,
+};
+pub use contract_spec::ContractSpec;
+pub use delivery::DeliverySchedule;
 pub use future_price::FuturePrice;
 pub use future_type::FutureType;
+pub use product::{ContractRule, FutureProduct};
 
 use crate::SmallStr;
-use anyhow::{Result, anyhow, bail};
+use anyhow::{Result, bail};
 use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use code::parse_code;
 use tea_calendar::{Calendar, china::CFFEX};
 
 const CFFEX_DEFAULT_CP_RATE: f64 = 0.03;
@@ -29,20 +43,11 @@ impl Default for Future {
 }
 
 impl Future {
+    /// 由期货代码构建, 统一为大写并规范化市场后缀, 不校验代码是否合法, 需要校验时使用`Future::parse`
     #[inline]
     pub fn new(code: impl AsRef<str>) -> Self {
-        let code = code.as_ref();
-        if let Some((code, market)) = code.split_once('.') {
-            Self {
-                code: code.into(),
-                market: Some(market.into()),
-            }
-        } else {
-            Self {
-                code: code.into(),
-                market: None,
-            }
-        }
+        let (code, market) = code::normalize(code.as_ref());
+        Self { code, market }
     }
 
     #[inline]
@@ -57,12 +62,6 @@ impl Future {
         self.shift_by_quarter(-3)
     }
 
-    #[inline]
-    pub fn future_type(&self) -> Result<FutureType> {
-        let typ = self.code.replace(|c: char| c.is_numeric(), "");
-        typ.parse()
-    }
-
     #[inline]
     /// 判断是否是可交割券
     ///
@@ -73,45 +72,37 @@ impl Future {
         maturity_date: NaiveDate,
         delivery_date: Option<NaiveDate>,
     ) -> Result<bool> {
-        Ok(self.future_type()?.is_deliverable(
-            delivery_date.unwrap_or_else(|| self.deliver_date().unwrap()),
-            carry_date,
-            maturity_date,
-        ))
+        let delivery_date = match delivery_date {
+            Some(date) => date,
+            None => self.deliver_date()?,
+        };
+        Ok(self
+            .contract_spec()?
+            .is_deliverable(delivery_date, carry_date, maturity_date))
     }
 
     /// 计算期货合约的最后交易日
     ///
     /// 计算国债期货的最后交易日=合约到期月份的第二个星期五
     /// 根据合约代码, 依据中金所的国债期货合约最后交易日的说, 返回该合约的最后交易日
-    /// 获取年月部分
+    /// 合约代码中的两位年份取最后交易日不早于品种上市日期的首个世纪, 品种未登记时返回错误
     pub fn last_trading_date(&self) -> Result<NaiveDate> {
-        let yymm = self.code.replace(|c: char| c.is_alphabetic(), "");
-        let yyyy = if let Some(yy) = yymm.get(0..2) {
-            format!("20{yy}")
+        let (_, yy, mm) = parse_code(self.code.as_str())?;
+        let listing_date = self.future_type()?.listing_start_date();
+        let listing_year = listing_date.year();
+        let year = listing_year - listing_year.rem_euclid(100) + yy as i32;
+        let last_trading_date = month_last_trading_date(year, mm);
+        if last_trading_date >= listing_date {
+            Ok(last_trading_date)
         } else {
-            bail!("Can not extract year from future code: {}", self.code);
-        };
-        let mm = if let Some(mm) = yymm.get(2..) {
-            mm
-        } else {
-            bail!("Can not extract month from future code: {}", self.code);
-        };
-        // 构造交割月的第一天
-        let begin_day_of_month = NaiveDate::from_ymd_opt(yyyy.parse()?, mm.parse()?, 1).unwrap();
-        // 第2个周五,月初首日的第0-6天不需要计算
-        for i in 7..14 {
-            let date_i = begin_day_of_month + Duration::days(i);
-            if let Weekday::Fri = date_i.weekday() {
-                return Ok(date_i);
-            }
+            Ok(month_last_trading_date(year + 100, mm))
         }
-        bail!("No valid trading date found")
     }
 
     /// 获取期货合约的配对缴款日
     ///
     /// 交割日为3天,其中第2天为缴款日,即最后交易日的第2个交易日,最后交易日一定为周五,所以缴款日一定是一个周二
+    /// 完整的交割流程及滚动交割参见`delivery_schedule`
     #[inline]
     pub fn deliver_date(&self) -> Result<NaiveDate> {
         let last_trading_date = self.last_trading_date()?;
@@ -159,12 +150,7 @@ impl Future {
         let types: Vec<FutureType> = if let Some(t) = future_type {
             vec![t]
         } else {
-            vec![
-                FutureType::TS,
-                FutureType::TF,
-                FutureType::T,
-                FutureType::TL,
-            ]
+            FutureType::all()
         };
 
         let mut res = Vec::new();
@@ -195,6 +181,17 @@ impl Future {
     }
 }
 
+/// 到期月份为year年month月的合约的最后交易日, 即该月的第二个星期五
+pub(super) fn month_last_trading_date(year: i32, month: u32) -> NaiveDate {
+    // 构造交割月的第一天
+    let begin_day_of_month = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    // 第2个周五,月初首日的第0-6天不需要计算
+    (7..14)
+        .map(|i| begin_day_of_month + Duration::days(i))
+        .find(|date| date.weekday() == Weekday::Fri)
+        .unwrap()
+}
+
 fn trading_futures_by_type(
     typ: FutureType,
     start: NaiveDate,
@@ -207,10 +204,13 @@ fn trading_futures_by_type(
         return Ok(result);
     }
 
-    let mut future = future_from_date(typ, start.max(listing_start));
-    while quarters_since_first(&future, typ)? < 0 {
-        future = future.next_future()?;
-    }
+    // 首批合约到期前从首批合约开始查找, 避免构造早于品种上市的合约
+    let first = Future::new(typ.first_contract());
+    let mut future = if start <= first.last_trading_date()? {
+        first
+    } else {
+        future_from_date(typ, start)
+    };
 
     loop {
         let (s, _) = future.trading_window()?;
@@ -242,35 +242,12 @@ fn future_from_date(typ: FutureType, date: NaiveDate) -> Future {
     Future::new(format!("{}{:02}{q_month:02}", typ.prefix(), year))
 }
 
-fn parse_code(code: &str) -> Result<(&str, u32, u32)> {
-    let (prefix, digits) = code
-        .char_indices()
-        .find(|(_, c)| c.is_ascii_digit())
-        .map(|(idx, _)| code.split_at(idx))
-        .ok_or_else(|| anyhow!("Invalid future code: {}", code))?;
-
-    if digits.len() != 4 {
-        bail!("Invalid future code: {}", code);
-    }
-
-    let yymm: u32 = digits.parse()?;
-    let year = yymm / 100;
-    let month = yymm % 100;
-
-    if !(1..=12).contains(&month) {
-        bail!("Invalid future month: {}", code);
-    }
-
-    Ok((prefix, year, month))
-}
-
 fn quarters_since_first(future: &Future, typ: FutureType) -> Result<i32> {
-    let (_, year, month) = parse_code(future.code.as_str())?;
-    let first_code = typ.first_contracts()[0];
-    let (_, base_year, base_month) = parse_code(first_code)?;
-    let month_index = year as i32 * 12 + month as i32;
-    let base_index = base_year as i32 * 12 + base_month as i32;
-    let diff = month_index - base_index;
+    let month_index = |f: &Future| -> Result<i32> {
+        let date = f.last_trading_date()?;
+        Ok(date.year() * 12 + date.month() as i32)
+    };
+    let diff = month_index(future)? - month_index(&Future::new(typ.first_contract()))?;
     if diff % 3 != 0 {
         bail!("Invalid future code month offset: {}", future.code);
     }
@@ -326,6 +303,33 @@ mod tests {
     use super::{Future, FutureType};
     use chrono::NaiveDate;
 
+    #[test]
+    fn last_trading_date_century() {
+        let d = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(
+            Future::new("TS2412").last_trading_date().unwrap(),
+            d(2024, 12, 13)
+        );
+        // 2年期国债期货2018年上市, 早于上市日期的年份属于下一个世纪
+        assert_eq!(
+            Future::new("TS0103").last_trading_date().unwrap(),
+            d(2101, 3, 11)
+        );
+        assert_eq!(
+            Future::new("TS1806").last_trading_date().unwrap(),
+            d(2118, 6, 10)
+        );
+        assert_eq!(
+            Future::new("TS1809").last_trading_date().unwrap(),
+            d(2018, 9, 14)
+        );
+        assert_eq!(
+            Future::new("TS1812").last_trading_date().unwrap(),
+            d(2018, 12, 14)
+        );
+        assert!(Future::new("X2412").last_trading_date().is_err());
+    }
+
     #[test]
     fn shift_across_year() {
         let f = Future::new("T2412");
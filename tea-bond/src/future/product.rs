@@ -0,0 +1,362 @@
+use super::{ContractSpec, FutureType};
+use crate::SmallStr;
+use crate::bond::{default_dir, deserialize_date, serialize_date};
+use anyhow::{Context, Result, ensure};
+use chrono::NaiveDate;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// 自某一日期起生效的合约规则
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ContractRule {
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub effective_date: NaiveDate, // 生效日期, 最后交易日不早于该日期的合约适用
+    #[serde(flatten)]
+    pub spec: ContractSpec,
+}
+
+/// 国债期货品种定义, 可以通过配置文件新增品种或修订历史规则
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FutureProduct {
+    pub prefix: SmallStr, // 合约代码前缀, 如"TF"
+    #[serde(default)]
+    pub name: SmallStr, // 品种名称
+    #[serde(
+        deserialize_with = "deserialize_date",
+        serialize_with = "serialize_date"
+    )]
+    pub listing_date: NaiveDate, // 品种上市日期, 即首批合约的首个交易日
+    pub first_contract: SmallStr, // 首批合约中最早到期的合约代码, 如"TF1312"
+    pub rules: Vec<ContractRule>, // 各时期的合约规则
+}
+
+/// 期货品种配置文件路径, 通过`FUTURE_PRODUCTS_PATH`指定, 未设置时使用`~/tea-bond/future_products.json`
+fn config_path() -> PathBuf {
+    std::env::var_os("FUTURE_PRODUCTS_PATH")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_dir().join("future_products.json"))
+}
+
+/// 中金所现有品种的历史规则, 按生效日期排列
+///
+/// 5年期和10年期国债期货自2020年3月合约起增加可交割券的发行期限上限并调整剩余期限范围,
+/// 修订前的规则只记录可交割券范围, 其余交易参数沿用现行规格
+fn cffex_rules(typ: FutureType, listing_date: NaiveDate) -> Vec<ContractRule> {
+    let current = ContractSpec::cffex(typ).unwrap();
+    let original = match typ {
+        // 合约到期月份首日剩余期限为4-7年的记账式附息国债, 不限发行期限
+        FutureType::TF => Some(ContractSpec {
+            max_issue_year: i32::MAX,
+            min_remain_year: 4.,
+            max_remain_year: Some(7.),
+            ..current
+        }),
+        // 合约到期月份首日剩余期限为6.5-10.25年的记账式附息国债, 不限发行期限
+        FutureType::T => Some(ContractSpec {
+            max_issue_year: i32::MAX,
+            min_remain_year: 6.5,
+            max_remain_year: Some(10.25),
+            ..current
+        }),
+        _ => None,
+    };
+    match original {
+        Some(spec) => vec![
+            ContractRule {
+                effective_date: listing_date,
+                spec,
+            },
+            ContractRule {
+                // TF1912/T1912最后交易日(2019-12-13)之后生效, 即自TF2003/T2003合约起适用
+                effective_date: NaiveDate::from_ymd_opt(2019, 12, 14).unwrap(),
+                spec: current,
+            },
+        ],
+        None => vec![ContractRule {
+            effective_date: listing_date,
+            spec: current,
+        }],
+    }
+}
+
+/// 已登记的期货品种, 初始化时载入中金所现有品种, 配置文件中的品种通过`FutureType::register_from_config`登记
+pub(crate) static PRODUCTS: LazyLock<RwLock<Vec<(FutureType, FutureProduct)>>> =
+    LazyLock::new(|| {
+        let products = [
+            (FutureType::TS, "2年期国债期货", (2018, 8, 17), "TS1812"),
+            (FutureType::TF, "5年期国债期货", (2013, 9, 6), "TF1312"),
+            (FutureType::T, "10年期国债期货", (2015, 3, 20), "T1509"),
+            (FutureType::TL, "30年期国债期货", (2023, 4, 21), "TL2306"),
+        ]
+        .into_iter()
+        .map(|(typ, name, (y, m, d), first_contract)| {
+            let listing_date = NaiveDate::from_ymd_opt(y, m, d).unwrap();
+            let product = FutureProduct {
+                prefix: typ.prefix().into(),
+                name: name.into(),
+                listing_date,
+                first_contract: first_contract.into(),
+                rules: cffex_rules(typ, listing_date),
+            };
+            (typ, product)
+        })
+        .collect();
+        RwLock::new(products)
+    });
+
+/// 校验期货品种定义
+fn validate(product: &FutureProduct) -> Result<()> {
+    ensure!(
+        !product.prefix.is_empty() && product.prefix.chars().all(|c| c.is_ascii_uppercase()),
+        "Future prefix should be uppercase letters, got {:?}",
+        product.prefix
+    );
+    ensure!(
+        !product.rules.is_empty(),
+        "Future product {} has no contract rule",
+        product.prefix
+    );
+    ensure!(
+        product
+            .first_contract
+            .strip_prefix(product.prefix.as_str())
+            .is_some_and(|yymm| yymm.len() == 4 && yymm.chars().all(|c| c.is_ascii_digit())),
+        "Invalid first contract {} of future product {}",
+        product.first_contract,
+        product.prefix
+    );
+    Ok(())
+}
+
+/// 登记已校验的期货品种, 已有相同前缀的品种时替换其定义
+fn insert(products: &mut Vec<(FutureType, FutureProduct)>, product: FutureProduct) -> FutureType {
+    if let Some((typ, p)) = products
+        .iter_mut()
+        .find(|(typ, _)| typ.prefix() == product.prefix)
+    {
+        *p = product;
+        return *typ;
+    }
+    // 新品种的前缀在进程内只登记一次, 泄漏的内存可以忽略
+    let typ = FutureType::from_static(product.prefix.to_string().leak());
+    products.push((typ, product));
+    typ
+}
+
+impl FutureProduct {
+    /// 从json配置文件读取期货品种定义, 文件内容为品种定义的数组
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Self>> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read future products from {:?}", path))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// 最后交易日为date的合约适用的规则, date早于全部规则的生效日期时使用最早的规则
+    pub fn rule(&self, date: NaiveDate) -> &ContractRule {
+        self.rules
+            .iter()
+            .filter(|r| r.effective_date <= date)
+            .max_by_key(|r| r.effective_date)
+            .or_else(|| self.rules.iter().min_by_key(|r| r.effective_date))
+            .unwrap()
+    }
+}
+
+impl FutureType {
+    /// 登记期货品种, 已有相同前缀的品种时替换其定义
+    #[inline]
+    pub fn register(product: FutureProduct) -> Result<Self> {
+        validate(&product)?;
+        Ok(insert(&mut PRODUCTS.write(), product))
+    }
+
+    /// 登记自rule.effective_date起生效的合约规则, 相同生效日期的规则会被替换, 可用于模拟历史规则变化
+    pub fn add_rule(&self, rule: ContractRule) {
+        let mut products = PRODUCTS.write();
+        let (_, product) = products
+            .iter_mut()
+            .find(|(typ, _)| typ == self)
+            .unwrap_or_else(|| panic!("Future type {} is not registered", self));
+        product
+            .rules
+            .retain(|r| r.effective_date != rule.effective_date);
+        product.rules.push(rule);
+    }
+
+    /// 从json配置文件登记期货品种, 返回登记的品种
+    ///
+    /// 文件中的全部品种校验通过后才会登记, 任一品种不合法时不登记任何品种
+    pub fn register_from_file(path: impl AsRef<Path>) -> Result<Vec<Self>> {
+        let path = path.as_ref();
+        let config = FutureProduct::load(path)?;
+        for product in &config {
+            validate(product).with_context(|| format!("Invalid future product in {:?}", path))?;
+        }
+        let mut products = PRODUCTS.write();
+        Ok(config
+            .into_iter()
+            .map(|product| insert(&mut products, product))
+            .collect())
+    }
+
+    /// 从`FUTURE_PRODUCTS_PATH`(默认为`~/tea-bond/future_products.json`)登记期货品种, 文件不存在时不登记
+    pub fn register_from_config() -> Result<Vec<Self>> {
+        let path = config_path();
+        if path.exists() {
+            Self::register_from_file(path)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Future;
+
+    #[test]
+    fn test_register_product() {
+        let json = r#"
+        [
+            {
+                "prefix": "TN",
+                "name": "7年期国债期货",
+                "listing_date": "2026-06-19",
+                "first_contract": "TN2609",
+                "rules": [
+                    {
+                        "effective_date": "2026-06-19",
+                        "contract_size": 1000000.0,
+                        "tick_size": 0.005,
+                        "price_limit": 0.015,
+                        "margin_ratio": 0.015,
+                        "fictitious_cp_rate": 0.03,
+                        "max_issue_year": 10,
+                        "min_remain_year": 5.0,
+                        "max_remain_year": 7.0
+                    },
+                    {
+                        "effective_date": "2030-01-01",
+                        "contract_size": 1000000.0,
+                        "tick_size": 0.005,
+                        "price_limit": 0.015,
+                        "margin_ratio": 0.015,
+                        "fictitious_cp_rate": 0.025,
+                        "max_issue_year": 10,
+                        "min_remain_year": 5.5,
+                        "max_remain_year": 7.25
+                    }
+                ]
+            }
+        ]
+        "#;
+        let path = std::env::temp_dir().join("tea_bond_test_future_products.json");
+        std::fs::write(&path, json).unwrap();
+        let types = FutureType::register_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let typ: FutureType = "TN".parse().unwrap();
+        assert_eq!(types, vec![typ]);
+        assert!(FutureType::all().contains(&typ));
+        assert_eq!(typ.product().name, "7年期国债期货");
+
+        let future = Future::new("TN2609");
+        assert_eq!(future.future_type().unwrap(), typ);
+        assert_eq!(
+            future.first_trading_date().unwrap(),
+            NaiveDate::from_ymd_opt(2026, 6, 19).unwrap()
+        );
+        assert_eq!(
+            future.last_trading_date().unwrap(),
+            NaiveDate::from_ymd_opt(2026, 9, 11).unwrap()
+        );
+        // 最后交易日早于品种上市日期的年份属于下一个世纪
+        assert_eq!(
+            Future::new("TN2603").last_trading_date().unwrap(),
+            NaiveDate::from_ymd_opt(2126, 3, 8).unwrap()
+        );
+        // 按合约最后交易日选取当时适用的规则
+        assert_eq!(future.contract_spec().unwrap().min_remain_year, 5.);
+        let spec = Future::new("TN3003").contract_spec().unwrap();
+        assert_eq!(spec.min_remain_year, 5.5);
+        assert_eq!(spec.fictitious_cp_rate, 0.025);
+        // 新规则提高了剩余期限下限
+        let carry_date = NaiveDate::from_ymd_opt(2025, 6, 15).unwrap();
+        let maturity_date = NaiveDate::from_ymd_opt(2035, 6, 15).unwrap();
+        assert!(
+            Future::new("TN2912")
+                .is_deliverable(carry_date, maturity_date, None)
+                .unwrap()
+        );
+        assert!(
+            !Future::new("TN3003")
+                .is_deliverable(carry_date, maturity_date, None)
+                .unwrap()
+        );
+        assert_eq!(
+            Future::trading_futures(
+                NaiveDate::from_ymd_opt(2026, 7, 1).unwrap(),
+                None,
+                Some(typ)
+            )
+            .unwrap(),
+            vec![
+                Future::new("TN2609"),
+                Future::new("TN2612"),
+                Future::new("TN2703")
+            ]
+        );
+
+        // 登记新的规则后按生效日期选取
+        let rule = ContractRule {
+            effective_date: NaiveDate::from_ymd_opt(2032, 1, 1).unwrap(),
+            spec: ContractSpec {
+                min_remain_year: 6.,
+                ..spec
+            },
+        };
+        typ.add_rule(rule);
+        typ.add_rule(rule);
+        assert_eq!(typ.product().rules.len(), 3);
+        assert_eq!(Future::new("TN3203").contract_spec().unwrap(), rule.spec);
+        assert_eq!(Future::new("TN3112").contract_spec().unwrap(), spec);
+
+        let invalid = FutureProduct {
+            first_contract: "T2609".into(),
+            ..typ.product()
+        };
+        assert!(FutureType::register(invalid).is_err());
+        let invalid = FutureProduct {
+            rules: vec![],
+            ..typ.product()
+        };
+        assert!(FutureType::register(invalid).is_err());
+        assert!("TX".parse::<FutureType>().is_err());
+
+        // 文件中有不合法的品种时不登记任何品种
+        let config = vec![
+            FutureProduct {
+                prefix: "TX".into(),
+                first_contract: "TX2609".into(),
+                ..typ.product()
+            },
+            FutureProduct {
+                prefix: "TY".into(),
+                first_contract: "TX2609".into(),
+                ..typ.product()
+            },
+        ];
+        let json = serde_json::to_string(&config).unwrap();
+        let path = std::env::temp_dir().join("tea_bond_test_invalid_future_products.json");
+        std::fs::write(&path, json).unwrap();
+        assert!(FutureType::register_from_file(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!("TX".parse::<FutureType>().is_err());
+    }
+}
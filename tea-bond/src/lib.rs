@@ -1,17 +1,32 @@
 mod bond;
+mod curve;
 pub mod day_counter;
 pub mod export;
 mod future;
 #[cfg(feature = "pnl")]
 pub mod pnl;
+mod solver;
 mod tf_evaluator;
 mod utils;
 
 pub use bond::{
-    Bond, BondDayCount, BondYtm, CachedBond, CouponType, InterestType, Market, WindSqlRow,
-    free_bond_map,
+    Bond, BondDayCount, BondYtm, CachedBond, CashFlow, CashFlowSchedule, CouponRateStep,
+    CouponType, EmbeddedOption, InterestType, Market, OptionType, PrincipalRepayment, RateFixings,
+    WindSqlRow, free_bond_map,
+};
+pub use curve::{
+    FitObjective, Interpolation, NssCurve, NssFitOptions, NssFitResult, NssModel, RateCurve,
+    YieldCurve,
+};
+pub use future::{
+    AdjustMode, CFFEX_MARKET, ContinuousBuilder, ContinuousSeries, ContractBar, ContractRule,
+    ContractSpec, DeliverySchedule, Future, FutureCodeError, FuturePrice, FutureProduct,
+    FutureType, RollEvent, RollRule,
+};
+pub use solver::YieldSolver;
+pub use tf_evaluator::{
+    CalendarSpread, CtdAnalysis, CtdScenario, DeliveryOption, HedgeContracts, RollWindow,
+    TfEvaluator, YieldModel, YieldShift, regression_beta,
 };
-pub use future::{Future, FuturePrice, FutureType};
-pub use tf_evaluator::TfEvaluator;
 
 pub type SmallStr = compact_str::CompactString;
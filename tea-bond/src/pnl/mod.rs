@@ -5,7 +5,7 @@ pub use fee::Fee;
 use std::path::PathBuf;
 pub use trade_from_signal::{QtyRoundMode, TradeFromPosOpt, trading_from_pos};
 
-use crate::{Bond, CachedBond};
+use crate::CachedBond;
 use chrono::NaiveDate;
 
 use anyhow::{Result, anyhow};
@@ -15,34 +15,10 @@ use serde::Deserialize;
 use tea_calendar::Calendar;
 use tevec::prelude::{EPS, IsNone, Number, Vec1, Vec1View};
 
-fn coupon_payment_count_between(
-    bond: &Bond,
-    from_exclusive: NaiveDate,
-    to_exclusive: NaiveDate,
-) -> Result<usize> {
-    if from_exclusive >= to_exclusive {
-        return Ok(0);
-    }
-    if bond.inst_freq <= 0 {
-        return Ok(0);
-    }
-    let (mut cp_date, _) = bond.get_nearest_cp_date(from_exclusive)?;
-    let offset = bond.get_cp_offset()?;
-    let mut count = 0;
-    while cp_date <= to_exclusive {
-        let payment_date = bond.mkt.find_workday(cp_date, 0);
-        if payment_date > from_exclusive && payment_date < to_exclusive {
-            count += 1;
-        }
-        cp_date = cp_date + offset;
-    }
-    Ok(count)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Market;
+    use crate::{Bond, Market};
     use tea_calendar::Calendar;
 
     #[test]
@@ -59,18 +35,16 @@ mod tests {
         let payment_day = NaiveDate::from_ymd_opt(2025, 9, 15).unwrap();
         let after_payment = NaiveDate::from_ymd_opt(2025, 9, 16).unwrap();
 
+        let schedule = bond.cash_flow_schedule().unwrap();
         assert_eq!(
-            coupon_payment_count_between(&bond, before_nominal, payment_day).unwrap(),
+            schedule.paid_between(before_nominal, payment_day).count(),
             0
         );
         assert_eq!(
-            coupon_payment_count_between(&bond, before_nominal, after_payment).unwrap(),
+            schedule.paid_between(before_nominal, after_payment).count(),
             1
         );
-        assert_eq!(
-            coupon_payment_count_between(&bond, payment_day, after_payment).unwrap(),
-            0
-        );
+        assert_eq!(schedule.paid_between(payment_day, after_payment).count(), 0);
     }
 
     #[test]
@@ -90,8 +64,11 @@ mod tests {
             bond.mkt.find_workday(nominal_coupon_date, 0),
             NaiveDate::from_ymd_opt(2025, 6, 3).unwrap()
         );
+        let schedule = bond.cash_flow_schedule().unwrap();
         assert_eq!(
-            coupon_payment_count_between(&bond, nominal_coupon_date, after_payment).unwrap(),
+            schedule
+                .paid_between(nominal_coupon_date, after_payment)
+                .count(),
             1
         );
     }
@@ -158,7 +135,12 @@ where
     } else {
         None
     };
-    let coupon_paid = symbol.as_ref().map(|bond| bond.get_coupon()).unwrap_or(0.);
+    // 现金流计划只需生成一次, 付息金额均以此为准
+    let schedule = match &symbol {
+        Some(bond) if !bond.is_zero_coupon() => Some(bond.cash_flow_schedule()?),
+        _ => None,
+    };
+    let mut coupon_paid = 0.; // 最近一次付息的金额
     let capital_rate_vec = match capital_rate_vec {
         Some(v) => Either::Left(
             v.titer()
@@ -206,11 +188,13 @@ where
                             * bond.par_value
                             * multiplier
                             / 365.;
+                        let schedule = schedule.as_ref().unwrap();
                         if let Some(last_settle_time) = last_settle_time {
-                            let coupon_count =
-                                coupon_payment_count_between(bond, last_settle_time, settle_time)?;
-                            state.coupon_paid +=
-                                coupon_count as f64 * coupon_paid * multiplier * state.pos;
+                            let coupon = schedule
+                                .paid_between(last_settle_time, settle_time)
+                                .map(|cf| cf.coupon)
+                                .sum::<f64>();
+                            state.coupon_paid += coupon * multiplier * state.pos;
                         }
                         if next_day_coupon != 0. {
                             state.coupon_paid += next_day_coupon;
@@ -220,7 +204,11 @@ where
                         let cp_dates = bond.get_nearest_cp_date(settle_time)?;
                         accrued_interest =
                             bond.calc_accrued_interest(settle_time, Some(cp_dates))?;
-                        last_cp_date = bond.mkt.find_workday(cp_dates.0, 0);
+                        let last_cf = schedule.iter().find(|cf| cf.nominal_date == cp_dates.0);
+                        last_cp_date = last_cf
+                            .map(|cf| cf.payment_date)
+                            .unwrap_or_else(|| bond.mkt.find_workday(cp_dates.0, 0));
+                        coupon_paid = last_cf.map(|cf| cf.coupon).unwrap_or(0.);
                         // 当天初始仓位会产生的票息
                         if settle_time == last_cp_date {
                             // 调节应计利息
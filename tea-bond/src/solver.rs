@@ -0,0 +1,295 @@
+use anyhow::{Result, bail, ensure};
+
+/// 收益率求解器
+///
+/// 优先使用牛顿迭代(需要解析导数), 迭代越界或不收敛时退回到区间内的Brent方法求解
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YieldSolver {
+    pub lower: f64,      // 求解区间下界
+    pub upper: f64,      // 求解区间上界
+    pub tol: f64,        // 收敛精度
+    pub max_iter: usize, // 最大迭代次数
+}
+
+impl Default for YieldSolver {
+    fn default() -> Self {
+        Self {
+            lower: -0.5,
+            upper: 2.,
+            tol: 1e-12,
+            max_iter: 100,
+        }
+    }
+}
+
+impl YieldSolver {
+    #[inline]
+    pub fn with_bracket(mut self, lower: f64, upper: f64) -> Self {
+        self.lower = lower;
+        self.upper = upper;
+        self
+    }
+
+    #[inline]
+    pub fn with_tol(mut self, tol: f64) -> Self {
+        self.tol = tol;
+        self
+    }
+
+    #[inline]
+    pub fn with_max_iter(mut self, max_iter: usize) -> Self {
+        self.max_iter = max_iter;
+        self
+    }
+
+    fn check(&self) -> Result<()> {
+        ensure!(
+            self.upper > self.lower,
+            "Invalid solver bracket: [{}, {}]",
+            self.lower,
+            self.upper
+        );
+        ensure!(self.tol > 0., "Solver tolerance should be positive");
+        Ok(())
+    }
+
+    /// 从初始值`guess`开始用牛顿法求解f(x) = 0, df为f的导数
+    ///
+    /// 迭代点越出求解区间、导数为0或达到最大迭代次数时改用Brent方法
+    pub fn solve<F, D>(&self, f: F, df: D, guess: f64) -> Result<f64>
+    where
+        F: Fn(f64) -> f64,
+        D: Fn(f64) -> f64,
+    {
+        self.check()?;
+        let mut x = if guess.is_finite() {
+            guess.clamp(self.lower, self.upper)
+        } else {
+            (self.lower + self.upper) * 0.5
+        };
+        for _ in 0..self.max_iter {
+            let (fx, dfx) = (f(x), df(x));
+            if fx == 0. {
+                return Ok(x);
+            }
+            if !fx.is_finite() || !dfx.is_finite() || dfx == 0. {
+                break;
+            }
+            let next = x - fx / dfx;
+            if !(self.lower..=self.upper).contains(&next) {
+                break;
+            }
+            if (next - x).abs() <= self.tol {
+                return Ok(next);
+            }
+            x = next;
+        }
+        self.brent(f)
+    }
+
+    /// 在求解区间内用Brent方法求解f(x) = 0
+    ///
+    /// 区间两端函数值同号时说明区间内无解, 返回错误
+    pub fn brent<F>(&self, f: F) -> Result<f64>
+    where
+        F: Fn(f64) -> f64,
+    {
+        self.check()?;
+        let (mut a, mut b) = (self.lower, self.upper);
+        let (mut fa, mut fb) = (f(a), f(b));
+        ensure!(
+            fa.is_finite() && fb.is_finite(),
+            "Function is not finite at the bracket [{}, {}]",
+            a,
+            b
+        );
+        if fa == 0. {
+            return Ok(a);
+        } else if fb == 0. {
+            return Ok(b);
+        }
+        ensure!(
+            fa.signum() != fb.signum(),
+            "No root in [{}, {}]: f(lower) = {}, f(upper) = {}",
+            a,
+            b,
+            fa,
+            fb
+        );
+        let (mut c, mut fc) = (a, fa);
+        let (mut d, mut e) = (b - a, b - a);
+        for _ in 0..self.max_iter {
+            if fb.signum() == fc.signum() {
+                (c, fc) = (a, fa);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+            let tol = 2. * f64::EPSILON * b.abs() + 0.5 * self.tol;
+            let m = 0.5 * (c - b);
+            if m.abs() <= tol || fb == 0. {
+                return Ok(b);
+            }
+            if e.abs() >= tol && fa.abs() > fb.abs() {
+                // 尝试割线法或逆二次插值
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (2. * m * s, 1. - s)
+                } else {
+                    let q = fa / fc;
+                    let r = fb / fc;
+                    (
+                        s * (2. * m * q * (q - r) - (b - a) * (r - 1.)),
+                        (q - 1.) * (r - 1.) * (s - 1.),
+                    )
+                };
+                if p > 0. {
+                    q = -q;
+                } else {
+                    p = -p;
+                }
+                if 2. * p < (3. * m * q - (tol * q).abs()).min((e * q).abs()) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = m;
+                    e = m;
+                }
+            } else {
+                // 二分
+                d = m;
+                e = m;
+            }
+            (a, fa) = (b, fb);
+            b += if d.abs() > tol { d } else { tol.copysign(m) };
+            fb = f(b);
+        }
+        bail!(
+            "Solver did not converge in {} iterations, last estimate: {}",
+            self.max_iter,
+            b
+        )
+    }
+}
+
+/// Nelder-Mead单纯形法求解无约束最小化问题
+///
+/// x0为初始点, step为各维度的初始步长, 单纯形各顶点函数值之差小于tol时停止, 返回(最优点, 最优值)
+pub(crate) fn nelder_mead<F>(
+    f: F,
+    x0: &[f64],
+    step: &[f64],
+    tol: f64,
+    max_iter: usize,
+) -> (Vec<f64>, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let n = x0.len();
+    // 非有限的函数值视为无穷大, 使单纯形远离该区域
+    let eval = |x: &[f64]| {
+        let v = f(x);
+        if v.is_finite() { v } else { f64::INFINITY }
+    };
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=n)
+        .map(|i| {
+            let mut x = x0.to_vec();
+            if i > 0 {
+                x[i - 1] += step[i - 1];
+            }
+            let v = eval(&x);
+            (x, v)
+        })
+        .collect();
+    for _ in 0..max_iter {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if (simplex[n].1 - simplex[0].1).abs() <= tol {
+            break;
+        }
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let towards = |coef: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&simplex[n].0)
+                .map(|(c, w)| c + coef * (w - c))
+                .collect()
+        };
+        let reflected = towards(-1.);
+        let fr = eval(&reflected);
+        if fr < simplex[0].1 {
+            let expanded = towards(-2.);
+            let fe = eval(&expanded);
+            simplex[n] = if fe < fr {
+                (expanded, fe)
+            } else {
+                (reflected, fr)
+            };
+        } else if fr < simplex[n - 1].1 {
+            simplex[n] = (reflected, fr);
+        } else {
+            let contracted = if fr < simplex[n].1 {
+                towards(-0.5)
+            } else {
+                towards(0.5)
+            };
+            let fc = eval(&contracted);
+            if fc < fr.min(simplex[n].1) {
+                simplex[n] = (contracted, fc);
+            } else {
+                // 向最优顶点收缩
+                let best = simplex[0].0.clone();
+                for (x, v) in simplex.iter_mut().skip(1) {
+                    for (xj, bj) in x.iter_mut().zip(&best) {
+                        *xj = bj + 0.5 * (*xj - bj);
+                    }
+                    *v = eval(x);
+                }
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::SQRT_2;
+
+    #[test]
+    fn test_yield_solver() {
+        let solver = YieldSolver::default().with_bracket(0., 2.);
+        // 正负两种单调方向
+        let x = solver.brent(|x: f64| x.powi(2) - 2.).unwrap();
+        assert!((x - SQRT_2).abs() <= 1e-10);
+        let x = solver.brent(|x: f64| -x.powi(2) + 2.).unwrap();
+        assert!((x - SQRT_2).abs() <= 1e-10);
+        let x = solver
+            .solve(|x: f64| x.powi(2) - 2., |x| 2. * x, 1.)
+            .unwrap();
+        assert!((x - SQRT_2).abs() <= 1e-12);
+        // 牛顿法越界时退回Brent方法
+        let x = solver
+            .solve(|x: f64| x.powi(2) - 2., |x| 2. * x, 1e-6)
+            .unwrap();
+        assert!((x - SQRT_2).abs() <= 1e-10);
+        // 区间内无解
+        assert!(solver.brent(|x: f64| x.powi(2) + 1.).is_err());
+        assert!(solver.with_bracket(2., 0.).brent(|x: f64| x - 1.).is_err());
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        // Rosenbrock函数, 最小值点为(1, 1)
+        let f = |x: &[f64]| (1. - x[0]).powi(2) + 100. * (x[1] - x[0] * x[0]).powi(2);
+        let (x, v) = nelder_mead(f, &[-1.2, 1.], &[0.5, 0.5], 1e-20, 5000);
+        assert!((x[0] - 1.).abs() < 1e-6 && (x[1] - 1.).abs() < 1e-6);
+        assert!(v < 1e-12);
+    }
+}
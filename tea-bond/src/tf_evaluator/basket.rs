@@ -0,0 +1,80 @@
+use super::TfEvaluator;
+use crate::bond::BondYtm;
+use crate::future::FuturePrice;
+use anyhow::{Context, Result, ensure};
+use chrono::NaiveDate;
+
+/// 可交割券篮子的最便宜可交割券(CTD)分析
+#[derive(Debug, Clone)]
+pub struct CtdAnalysis {
+    // 可交割券的计算结果, 按IRR从高到低排列, 首个即为CTD
+    pub evaluators: Vec<TfEvaluator>,
+}
+
+impl CtdAnalysis {
+    /// 对一篮子债券进行CTD分析
+    ///
+    /// 剔除非可交割券及IRR无法计算(如未提供收益率)的债券后, 按IRR从高到低排序
+    pub fn new(
+        date: NaiveDate,
+        future: impl Into<FuturePrice>,
+        bonds: impl IntoIterator<Item = BondYtm>,
+        capital_rate: f64,
+        reinvest_rate: Option<f64>,
+    ) -> Result<Self> {
+        let future = future.into();
+        let deliver_date = future.deliver_date()?;
+        let mut evaluators = Vec::new();
+        for bond in bonds {
+            let evaluator = TfEvaluator {
+                date,
+                future: future.clone(),
+                bond,
+                capital_rate,
+                reinvest_rate,
+                deliver_date: Some(deliver_date),
+                ..Default::default()
+            };
+            if !evaluator.is_deliverable()? || evaluator.bond.ytm().is_nan() {
+                continue;
+            }
+            let code = evaluator.bond.code().to_string();
+            let evaluator = evaluator
+                .with_net_basis_spread()
+                .and_then(|e| e.with_irr())
+                .with_context(|| format!("Failed to evaluate deliverable bond {code}"))?;
+            if evaluator.irr.unwrap().is_finite() {
+                evaluators.push(evaluator);
+            }
+        }
+        ensure!(
+            !evaluators.is_empty(),
+            "No deliverable bond for future {} on {}",
+            future.code,
+            date
+        );
+        evaluators.sort_by(|a, b| b.irr.unwrap().total_cmp(&a.irr.unwrap()));
+        Ok(Self { evaluators })
+    }
+
+    /// 最便宜可交割券, 即IRR最高的可交割券
+    #[inline]
+    pub fn ctd(&self) -> &TfEvaluator {
+        &self.evaluators[0]
+    }
+
+    /// CTD与次便宜券的IRR之差, 篮子中仅有一只可交割券时返回None
+    #[inline]
+    pub fn irr_gap(&self) -> Option<f64> {
+        let second = self.evaluators.get(1)?;
+        Some(self.ctd().irr.unwrap() - second.irr.unwrap())
+    }
+
+    /// 债券在篮子中的排名(从0开始), 不在篮子中时返回None
+    #[inline]
+    pub fn rank(&self, code: &str) -> Option<usize> {
+        self.evaluators
+            .iter()
+            .position(|e| e.bond.code() == code || e.bond.bond_code() == code)
+    }
+}
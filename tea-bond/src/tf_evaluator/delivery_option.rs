@@ -0,0 +1,168 @@
+use super::{CtdAnalysis, TfEvaluator};
+use anyhow::{Result, bail, ensure};
+use chrono::NaiveDate;
+use std::str::FromStr;
+
+/// 交割日收益率的概率分布模型
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum YieldModel {
+    // 正态模型, 收益率变动 = 波动率 * sqrt(T) * z, 波动率为收益率的绝对波动(如0.005)
+    #[default]
+    Normal,
+    // 对数正态模型, 收益率按exp(波动率 * sqrt(T) * z - 波动率^2 * T / 2)比例变动
+    Lognormal,
+}
+
+impl FromStr for YieldModel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Normal" | "normal" => Ok(YieldModel::Normal),
+            "Lognormal" | "lognormal" | "log_normal" => Ok(YieldModel::Lognormal),
+            _ => bail!("Unknown yield model: {}", s),
+        }
+    }
+}
+
+/// 数值积分的标准正态分位点个数, 在[-6, 6]区间内等距分布
+const GRID_SIZE: usize = 241;
+const GRID_BOUND: f64 = 6.;
+
+/// 交割期权(品质期权)估值结果, 向量字段与CtdAnalysis.evaluators一一对应
+#[derive(Debug, Clone)]
+pub struct DeliveryOption {
+    pub option_value: f64,              // 交割期权价值, 以期货价格计
+    pub theoretical_price: f64,         // 期货理论价格, 交割日各券折算价格最小值的期望
+    pub forward_ytms: Vec<f64>,         // 各券的交割日远期收益率
+    pub ctd_probabilities: Vec<f64>,    // 各券在交割日成为CTD的概率
+    pub oa_net_basis_spreads: Vec<f64>, // 期权调整净基差 = 净基差 - 转换因子 * 交割期权价值
+    pub oa_future_dv01: f64,            // 期权调整期货DV01, 收益率整体下行1bp时期货理论价格的变动
+}
+
+/// 单只可交割券在交割日按收益率折算的期货价格
+struct DeliverableQuote<'a> {
+    evaluator: &'a TfEvaluator,
+    deliver_date: NaiveDate,
+    deliver_accrued_interest: f64,
+}
+
+impl DeliverableQuote<'_> {
+    /// 交割日收益率对应的净价除以转换因子
+    #[inline]
+    fn converted_price(&self, ytm: f64) -> Result<f64> {
+        let dirty_price =
+            self.evaluator
+                .bond
+                .calc_dirty_price_with_ytm(ytm, self.deliver_date, None, None)?;
+        Ok((dirty_price - self.deliver_accrued_interest) / self.evaluator.cf.unwrap())
+    }
+
+    /// 远期净价(净价-持有收益)对应的交割日收益率
+    #[inline]
+    fn forward_ytm(&self) -> Result<f64> {
+        let forward_clean = self.evaluator.clean_price.unwrap() - self.evaluator.carry.unwrap();
+        self.evaluator.bond.calc_ytm_with_price(
+            forward_clean + self.deliver_accrued_interest,
+            self.deliver_date,
+            None,
+            None,
+        )
+    }
+}
+
+impl CtdAnalysis {
+    /// 交割期权估值
+    ///
+    /// 假设各可交割券收益率在交割日同向变动, 对收益率分布数值积分得到交割日各券折算价格最小值的期望,
+    /// 交割期权价值 = 当前CTD折算价格的期望 - 各券折算价格最小值的期望, 即空头择券带来的价值
+    ///
+    /// vol: 年化收益率波动率, 正态模型为绝对波动, 对数正态模型为相对波动
+    pub fn delivery_option(&self, model: YieldModel, vol: f64) -> Result<DeliveryOption> {
+        ensure!(
+            vol >= 0.,
+            "Yield volatility should be non-negative, got {}",
+            vol
+        );
+        let ctd = self.ctd();
+        let deliver_date = ctd.deliver_date.unwrap();
+        let quotes = self
+            .evaluators
+            .iter()
+            .map(|evaluator| {
+                Ok(DeliverableQuote {
+                    evaluator,
+                    deliver_date,
+                    deliver_accrued_interest: evaluator
+                        .bond
+                        .calc_accrued_interest(deliver_date, None)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let forward_ytms = quotes
+            .iter()
+            .map(DeliverableQuote::forward_ytm)
+            .collect::<Result<Vec<_>>>()?;
+        if model == YieldModel::Lognormal {
+            ensure!(
+                forward_ytms.iter().all(|y| *y > 0.),
+                "Lognormal yield model requires positive forward yields"
+            );
+        }
+        // 标准正态分布的离散化
+        let t = ctd.remain_days_to_deliver.unwrap() as f64 / 365.;
+        let sigma = vol * t.sqrt();
+        let step = 2. * GRID_BOUND / (GRID_SIZE - 1) as f64;
+        let nodes: Vec<(f64, f64)> = (0..GRID_SIZE)
+            .map(|k| {
+                let z = -GRID_BOUND + step * k as f64;
+                (z, (-0.5 * z * z).exp())
+            })
+            .collect();
+        let total: f64 = nodes.iter().map(|(_, w)| w).sum();
+        let shocked = |ytm: f64, z: f64, bump: f64| match model {
+            YieldModel::Normal => ytm + sigma * z + bump,
+            YieldModel::Lognormal => ytm * (sigma * z - 0.5 * sigma * sigma).exp() + bump,
+        };
+        // 返回(各券折算价格最小值的期望, 当前CTD折算价格的期望, 各券成为CTD的概率)
+        let expectation = |bump: f64| -> Result<(f64, f64, Vec<f64>)> {
+            let mut cheapest = 0.;
+            let mut ctd_price = 0.;
+            let mut probabilities = vec![0.; quotes.len()];
+            for (z, w) in &nodes {
+                let w = w / total;
+                let prices = quotes
+                    .iter()
+                    .zip(&forward_ytms)
+                    .map(|(quote, ytm)| quote.converted_price(shocked(*ytm, *z, bump)))
+                    .collect::<Result<Vec<_>>>()?;
+                let mut idx = 0;
+                for (i, price) in prices.iter().enumerate() {
+                    if *price < prices[idx] {
+                        idx = i;
+                    }
+                }
+                cheapest += w * prices[idx];
+                ctd_price += w * prices[0];
+                probabilities[idx] += w;
+            }
+            Ok((cheapest, ctd_price, probabilities))
+        };
+        let (theoretical_price, ctd_price, ctd_probabilities) = expectation(0.)?;
+        let option_value = ctd_price - theoretical_price;
+        let oa_future_dv01 = (expectation(-1e-4)?.0 - expectation(1e-4)?.0) / 2.;
+        let oa_net_basis_spreads = self
+            .evaluators
+            .iter()
+            .map(|e| e.net_basis_spread.unwrap() - e.cf.unwrap() * option_value)
+            .collect();
+        Ok(DeliveryOption {
+            option_value,
+            theoretical_price,
+            forward_ytms,
+            ctd_probabilities,
+            oa_net_basis_spreads,
+            oa_future_dv01,
+        })
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     future::{FuturePrice, calc_cf},
     utils::month_delta,
 };
-use anyhow::{Result, ensure};
+use anyhow::Result;
 use chrono::NaiveDate;
 
 #[derive(Clone, smart_default::SmartDefault)]
@@ -101,6 +101,29 @@ impl TfEvaluator {
         Ok(self)
     }
 
+    /// 按交割意向申报日为intention_date的滚动交割重新构建评估器
+    ///
+    /// 缴款日替换为滚动交割的缴款日, 发票价格、持有收益和IRR等均按该日计算;
+    /// 转换因子由合约确定, 保持集中交割时的数值
+    pub fn early_delivery(self, intention_date: NaiveDate) -> Result<Self> {
+        let schedule = self.future.delivery_schedule(Some(intention_date))?;
+        let out = Self {
+            date: self.date,
+            future: self.future,
+            bond: self.bond,
+            capital_rate: self.capital_rate,
+            reinvest_rate: self.reinvest_rate,
+            cf: self.cf,
+            ..Default::default()
+        }
+        .with_cf()?;
+        Ok(Self {
+            deliver_date: Some(schedule.payment_date),
+            deliver_cp_dates: None,
+            ..out
+        })
+    }
+
     /// 计算前一付息日和下一付息日
     #[inline]
     pub fn with_nearest_cp_dates(mut self) -> Self {
@@ -225,7 +248,7 @@ impl TfEvaluator {
                 out.bond.cp_rate,
                 out.bond.inst_freq,
                 month_num_from_dlv2next_cp,
-                None,
+                Some(out.future.contract_spec()?.fictitious_cp_rate),
             ));
             Ok(out)
         } else {
@@ -256,7 +279,7 @@ impl TfEvaluator {
         if self.deliver_accrued_interest.is_none() {
             let mut out = self.with_deliver_cp_dates()?;
             let coupon = out.bond.get_coupon();
-            let deliver_date = out.future.deliver_date()?; // 交割日
+            let deliver_date = out.deliver_date.unwrap(); // 交割日
             let (deliver_pre_cp_date, deliver_next_cp_date) = out.deliver_cp_dates.unwrap();
             let deliver_accrued_interest = coupon
                 * ACTUAL.count_days(deliver_pre_cp_date, deliver_date) as f64
@@ -281,35 +304,37 @@ impl TfEvaluator {
         }
     }
 
+    /// 计算交割货款
+    ///
+    /// 交割货款=期货全价(发票价格)*合约乘数*交割手数
+    pub fn invoice_amount(self, contracts: f64) -> Result<f64> {
+        let multiplier = self.future.contract_spec()?.multiplier();
+        let out = self.with_future_dirty_price()?;
+        Ok(out.future_dirty_price.unwrap() * multiplier * contracts)
+    }
+
     /// 计算期间付息
     pub fn with_remain_cp_to_deliver(self) -> Result<Self> {
         if self.remain_cp_to_deliver.is_none() {
             let mut out = self.with_deliver_date()?.with_nearest_cp_dates();
             let deliver_date = out.deliver_date.unwrap();
-            // 计算期间付息次数
-            let n = out.bond.remain_cp_num_until(
-                out.date,
-                deliver_date,
-                out.cp_dates.map(|ds| ds.1),
-            )?;
-            if n != 0 {
-                let coupon = out.bond.get_coupon();
-                let remain_cp_dates = out.bond.remain_cp_dates_until(
-                    out.date,
-                    deliver_date,
-                    out.cp_dates.map(|ds| ds.1),
-                )?;
-                ensure!(remain_cp_dates.len() == n as usize, "implement error");
-                out.remain_cp_to_deliver = Some(coupon * n as f64);
-                // 加权平均期间付息,按每个付息日到结算日的年化剩余天数加权的实际付息
-                out.remain_cp_to_deliver_wm = Some(
-                    remain_cp_dates.into_iter().fold(0., |acc, d| {
-                        acc + ACTUAL.count_days(d, deliver_date) as f64 / 365.
-                    }) * coupon,
-                );
-            } else {
+            // 计算期间付息
+            let next_cp_date = out.cp_dates.map(|ds| ds.1);
+            if out.bond.is_zero_coupon() || next_cp_date.is_some_and(|d| d > deliver_date) {
                 out.remain_cp_to_deliver = Some(0.);
                 out.remain_cp_to_deliver_wm = Some(0.);
+            } else {
+                let next_cp_date = match next_cp_date {
+                    Some(d) => d,
+                    None => out.bond.get_nearest_cp_date(out.date)?.1,
+                };
+                let schedule = out.bond.cash_flow_schedule()?;
+                let flows = schedule.between(next_cp_date, deliver_date);
+                out.remain_cp_to_deliver = Some(flows.iter().map(|cf| cf.coupon).sum());
+                // 加权平均期间付息,按每个付息日到结算日的年化剩余天数加权的实际付息
+                out.remain_cp_to_deliver_wm = Some(flows.iter().fold(0., |acc, cf| {
+                    acc + ACTUAL.count_days(cf.nominal_date, deliver_date) as f64 / 365. * cf.coupon
+                }));
             }
             Ok(out)
         } else {
@@ -418,6 +443,24 @@ impl TfEvaluator {
         Ok(out.clean_price.unwrap() - neutral_cf * out.future.price - out.carry.unwrap())
     }
 
+    /// 由期货全价(发票价格)计算内部收益率, 需已计算现券全价、剩余天数和期间付息
+    fn irr_with_future_dirty_price(&self, future_dirty_price: f64) -> f64 {
+        let dirty_price = self.dirty_price.unwrap();
+        let remain_days = self.remain_days_to_deliver.unwrap() as f64;
+        let (cp, cp_wm) = (
+            self.remain_cp_to_deliver.unwrap(),
+            self.remain_cp_to_deliver_wm.unwrap(),
+        );
+        if let Some(reinvest_rate) = self.reinvest_rate {
+            // 如果定义了利息再投资利率则需要将使用加权平均期间付息乘以该再投资利率
+            let irr = (future_dirty_price + cp + cp_wm * reinvest_rate) / dirty_price - 1.;
+            irr * 365. / remain_days
+        } else {
+            // QB: irr=(发票价格+期间付息-现券全价)/(现券全价*剩余天数/365-加权平均期间付息)
+            (future_dirty_price + cp - dirty_price) / (dirty_price * remain_days / 365. - cp_wm)
+        }
+    }
+
     /// 计算内部收益率IRR
     pub fn with_irr(self) -> Result<Self> {
         if self.irr.is_none() {
@@ -426,31 +469,51 @@ impl TfEvaluator {
                 .with_future_dirty_price()?
                 .with_remain_days_to_deliver()?
                 .with_remain_cp_to_deliver()?;
-            if let Some(reinvest_rate) = out.reinvest_rate {
-                // 如果定义了利息再投资利率则需要将使用加权平均期间付息乘以该再投资利率
-                let irr = (out.future_dirty_price.unwrap()
-                    + out.remain_cp_to_deliver.unwrap()
-                    + out.remain_cp_to_deliver_wm.unwrap() * reinvest_rate)
-                    / out.dirty_price.unwrap()
-                    - 1.;
-                let irr = irr * 365. / out.remain_days_to_deliver.unwrap() as f64;
-                out.irr = Some(irr);
-            } else {
-                // QB: irr=(发票价格+期间付息-现券全价)/(现券全价*剩余天数/365-加权平均期间付息)
-                out.irr = Some(
-                    (out.future_dirty_price.unwrap() + out.remain_cp_to_deliver.unwrap()
-                        - out.dirty_price.unwrap())
-                        / (out.dirty_price.unwrap() * out.remain_days_to_deliver.unwrap() as f64
-                            / 365.
-                            - out.remain_cp_to_deliver_wm.unwrap()),
-                );
-            }
+            out.irr = Some(out.irr_with_future_dirty_price(out.future_dirty_price.unwrap()));
             Ok(out)
         } else {
             Ok(self)
         }
     }
 
+    /// 计算给定期货价格对应的隐含回购利率, 与IRR的计算口径一致
+    ///
+    /// 以发票价格作为交割日的远期全价, 由`Bond::calc_implied_repo`计算
+    pub fn implied_repo(self, future_price: f64) -> Result<f64> {
+        let out = self
+            .with_dirty_price()?
+            .with_cf()?
+            .with_deliver_accrued_interest()?;
+        let future_dirty_price =
+            future_price * out.cf.unwrap() + out.deliver_accrued_interest.unwrap();
+        out.bond.calc_implied_repo(
+            out.dirty_price.unwrap(),
+            out.date,
+            out.deliver_date.unwrap(),
+            future_dirty_price,
+            out.reinvest_rate,
+        )
+    }
+
+    /// 计算期货理论价格, 即IRR等于回购利率时的期货价格
+    ///
+    /// 期货理论价格 = (远期全价 - 交割应计利息) / 转换因子, 远期全价由`Bond::calc_forward_dirty_price`计算,
+    /// 未定义再投资利率时期间付息按回购利率再投资
+    pub fn theoretical_price(self, repo_rate: f64) -> Result<f64> {
+        let out = self
+            .with_dirty_price()?
+            .with_cf()?
+            .with_deliver_accrued_interest()?;
+        let forward_dirty_price = out.bond.calc_forward_dirty_price(
+            out.dirty_price.unwrap(),
+            out.date,
+            out.deliver_date.unwrap(),
+            repo_rate,
+            out.reinvest_rate,
+        )?;
+        Ok((forward_dirty_price - out.deliver_accrued_interest.unwrap()) / out.cf.unwrap())
+    }
+
     /// 计算期货隐含收益率
     ///
     /// `use_deliver_date`: 是否使用交割日进行计算
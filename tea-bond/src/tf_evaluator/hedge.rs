@@ -0,0 +1,69 @@
+use super::TfEvaluator;
+use crate::bond::BondYtm;
+use anyhow::{Result, ensure};
+
+/// 债券头寸的期货套保手数, 正数表示卖出期货, 负数表示买入期货
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HedgeContracts {
+    pub contract_size: f64, // 合约面值(元)
+    pub dv01: f64,          // DV01中性套保手数
+    pub cf: f64,            // 转换因子比率套保手数
+    pub beta: Option<f64>,  // 回归beta套保手数
+}
+
+/// 由债券价格和期货价格序列估计回归beta, 即债券价格变动对期货价格变动的OLS斜率
+pub fn regression_beta(bond_prices: &[f64], future_prices: &[f64]) -> Result<f64> {
+    ensure!(
+        bond_prices.len() == future_prices.len(),
+        "Length of bond prices and future prices mismatch"
+    );
+    // 使用两者均有效的相邻价格变动
+    let changes: Vec<(f64, f64)> = bond_prices
+        .windows(2)
+        .zip(future_prices.windows(2))
+        .map(|(b, f)| (b[1] - b[0], f[1] - f[0]))
+        .filter(|(b, f)| b.is_finite() && f.is_finite())
+        .collect();
+    ensure!(
+        changes.len() >= 2,
+        "At least 2 valid price changes are required to estimate beta"
+    );
+    let n = changes.len() as f64;
+    let (mean_b, mean_f) = changes
+        .iter()
+        .fold((0., 0.), |(sb, sf), (b, f)| (sb + b / n, sf + f / n));
+    let (cov, var) = changes.iter().fold((0., 0.), |(cov, var), (b, f)| {
+        (
+            cov + (b - mean_b) * (f - mean_f),
+            var + (f - mean_f) * (f - mean_f),
+        )
+    });
+    ensure!(var > 0., "Future price changes have zero variance");
+    Ok(cov / var)
+}
+
+impl TfEvaluator {
+    /// 计算面值为face_value(元)的债券头寸所需的期货套保手数
+    ///
+    /// - DV01中性: 手数 = 面值 * 债券DV01 / (合约面值 * 期货DV01), 期货DV01默认按当前债券作为CTD计算
+    /// - 转换因子比率: 手数 = 面值 * 转换因子 / 合约面值
+    /// - 回归beta: 手数 = 面值 * beta / 合约面值, beta为债券价格变动对期货价格变动的回归系数
+    pub fn hedge_contracts(
+        self,
+        face_value: f64,
+        ctd: Option<BondYtm>,
+        beta: Option<f64>,
+    ) -> Result<HedgeContracts> {
+        let contract_size = self.future.contract_spec()?.contract_size;
+        let out = self.with_cf()?;
+        let dv01 = out.clone().dv01()?;
+        let future_dv01 = out.clone().future_dv01(ctd)?;
+        let notional = face_value / contract_size;
+        Ok(HedgeContracts {
+            contract_size,
+            dv01: notional * dv01 / future_dv01,
+            cf: notional * out.cf.unwrap(),
+            beta: beta.map(|beta| notional * beta),
+        })
+    }
+}
@@ -1,8 +1,18 @@
+mod basket;
+mod delivery_option;
 mod evaluator;
+mod hedge;
 mod impl_traits;
+mod roll;
+mod scenario;
 mod update_with_new_info;
 
+pub use basket::CtdAnalysis;
+pub use delivery_option::{DeliveryOption, YieldModel};
 pub use evaluator::TfEvaluator;
+pub use hedge::{HedgeContracts, regression_beta};
+pub use roll::{CalendarSpread, RollWindow};
+pub use scenario::{CtdScenario, YieldShift};
 
 #[cfg(test)]
 mod tests {
@@ -184,6 +194,311 @@ mod tests {
         assert_approx_eq(evaluator.future_ytm, 0.024131859300099628);
     }
 
+    fn get_basket() -> Vec<BondYtm> {
+        let bond_str = r#"
+            {
+                "bond_code": "230026.IB",
+                "mkt": "IB",
+                "abbr": "23附息国债26",
+                "par_value": 100.0,
+                "cp_type": "Coupon_Bear",
+                "interest_type": "Fixed",
+                "cp_rate_1st": 0.0267,
+                "base_rate": null,
+                "rate_spread": null,
+                "inst_freq": 2,
+                "carry_date": "2023-11-25",
+                "maturity_date": "2033-11-25",
+                "day_count": "ACT/ACT"
+            }
+        "#;
+        let bond: Bond = serde_json::from_str(bond_str).unwrap();
+        let evaluator = get_evaluator();
+        // 剩余期限不足6.5年, 不是T合约的可交割券
+        let short_bond = Bond {
+            bond_code: "249999.IB".into(),
+            maturity_date: NaiveDate::from_ymd_opt(2029, 3, 25).unwrap(),
+            ..(*evaluator.bond.bond).clone()
+        };
+        vec![
+            BondYtm::new(bond, 0.0218),
+            evaluator.bond.clone(),
+            BondYtm::new(short_bond, 0.02),
+        ]
+    }
+
+    #[test]
+    fn test_ctd_analysis() {
+        let evaluator = get_evaluator();
+        let bonds = get_basket();
+        let analysis = CtdAnalysis::new(
+            evaluator.date,
+            evaluator.future.clone(),
+            bonds,
+            evaluator.capital_rate,
+            None,
+        )
+        .unwrap();
+        assert_eq!(analysis.evaluators.len(), 2);
+        assert_eq!(analysis.ctd().bond.code(), "240006");
+        assert_approx_eq(analysis.ctd().irr, 0.028581046130913425);
+        assert_approx_eq(analysis.ctd().net_basis_spread, -0.1016208902580227);
+        let gap = analysis.irr_gap().unwrap();
+        assert!(gap > 0.);
+        assert_approx_eq(
+            analysis.evaluators[1].irr,
+            analysis.ctd().irr.unwrap() - gap,
+        );
+        assert_eq!(analysis.rank("230026.IB"), Some(1));
+        assert_eq!(analysis.rank("249999"), None);
+        assert!(CtdAnalysis::new(evaluator.date, evaluator.future, vec![], 0.019, None).is_err());
+    }
+
+    #[test]
+    fn test_ctd_scenarios() {
+        let evaluator = get_evaluator();
+        let shifts = YieldShift::grid(&[-0.01, 0., 0.01], &[0.], 7.);
+        let scenarios = CtdAnalysis::scenarios(
+            evaluator.date,
+            evaluator.future.clone(),
+            get_basket(),
+            evaluator.capital_rate,
+            None,
+            &shifts,
+        )
+        .unwrap();
+        assert_eq!(scenarios.len(), 3);
+        // 收益率不变时还原当前期货价格与CTD
+        assert!((scenarios[1].future_price - 105.5).abs() < 1e-10);
+        assert_eq!(scenarios[1].ctd().bond.code(), "240006");
+        assert_approx_eq(scenarios[1].ctd().irr, 0.028581046130913425);
+        // 收益率上行时期货价格下跌, 久期更长的债券成为CTD
+        assert!(scenarios[0].future_price > 105.5 && scenarios[2].future_price < 105.5);
+        assert_eq!(scenarios[0].ctd().bond.code(), "240006");
+        assert_eq!(scenarios[2].ctd().bond.code(), "230026");
+        assert!(scenarios[2].future_ytm().unwrap() > scenarios[1].future_ytm().unwrap());
+        // 扭转情景中支点期限处的收益率不变
+        let twist = YieldShift::new(0., 0.001, 7.);
+        assert_eq!(twist.shift_at(7.), 0.);
+        assert!((twist.shift_at(9.) - 0.002).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_delivery_option() {
+        let evaluator = get_evaluator();
+        // 收益率上行1%后久期更长的230026成为CTD
+        let analysis = CtdAnalysis::scenarios(
+            evaluator.date,
+            evaluator.future.clone(),
+            get_basket(),
+            evaluator.capital_rate,
+            None,
+            &[YieldShift::new(0.01, 0., 7.)],
+        )
+        .unwrap()
+        .remove(0)
+        .analysis;
+        assert_eq!(analysis.ctd().bond.code(), "230026");
+        // 波动率为0时交割期权没有价值, 期货DV01等于CTD远期DV01除以转换因子
+        let option = analysis.delivery_option(YieldModel::Normal, 0.).unwrap();
+        assert!(option.option_value.abs() < 1e-12);
+        assert!((option.ctd_probabilities[0] - 1.).abs() < 1e-12);
+        assert_eq!(option.ctd_probabilities[1], 0.);
+        let ctd = analysis.ctd();
+        assert_approx_eq(
+            Some(option.oa_net_basis_spreads[0]),
+            ctd.net_basis_spread.unwrap(),
+        );
+        let deliver_date = ctd.deliver_date.unwrap();
+        let forward_dv01 = ctd
+            .bond
+            .calc_dv01(option.forward_ytms[0], deliver_date, None, None)
+            .unwrap();
+        let zero_vol_dv01 = option.oa_future_dv01;
+        assert!((zero_vol_dv01 - forward_dv01 / ctd.cf.unwrap()).abs() < 1e-4);
+
+        for model in [YieldModel::Normal, YieldModel::Lognormal] {
+            let vol = if model == YieldModel::Normal {
+                0.005
+            } else {
+                0.25
+            };
+            let option = analysis.delivery_option(model, vol).unwrap();
+            assert!(option.option_value > 0.);
+            assert!((option.ctd_probabilities.iter().sum::<f64>() - 1.).abs() < 1e-12);
+            assert!(option.ctd_probabilities[1] > 0.);
+            for (e, oa) in analysis.evaluators.iter().zip(&option.oa_net_basis_spreads) {
+                assert!(*oa < e.net_basis_spread.unwrap());
+            }
+            // 收益率下行时空头转而交割久期较短的券, 择券使期货DV01低于波动率为0时的DV01
+            assert!(option.oa_future_dv01 > 0.);
+            assert!(
+                option.oa_future_dv01 < zero_vol_dv01,
+                "{model:?} {} >= {zero_vol_dv01}",
+                option.oa_future_dv01
+            );
+        }
+        // 单只可交割券时没有择券价值
+        let single = CtdAnalysis::new(
+            evaluator.date,
+            evaluator.future.clone(),
+            vec![evaluator.bond.clone()],
+            evaluator.capital_rate,
+            None,
+        )
+        .unwrap();
+        let option = single.delivery_option(YieldModel::Normal, 0.005).unwrap();
+        assert!(option.option_value.abs() < 1e-12);
+        assert!(analysis.delivery_option(YieldModel::Normal, -0.01).is_err());
+        assert_eq!(
+            "lognormal".parse::<YieldModel>().unwrap(),
+            YieldModel::Lognormal
+        );
+    }
+
+    #[test]
+    fn test_theoretical_price() {
+        for evaluator in [
+            get_evaluator(),
+            get_evaluator_without_cp_before_deliver_date(),
+        ] {
+            let evaluator = evaluator.calc_all().unwrap();
+            // IRR对应的理论价格还原期货价格
+            let irr = evaluator.irr.unwrap();
+            let price = evaluator.clone().theoretical_price(irr).unwrap();
+            assert!((price - evaluator.future.price).abs() < 1e-10);
+            assert_approx_eq(
+                Some(
+                    evaluator
+                        .clone()
+                        .implied_repo(evaluator.future.price)
+                        .unwrap(),
+                ),
+                irr,
+            );
+            // 回购利率越高, 持有成本越高, 期货理论价格越高
+            let price = evaluator.clone().theoretical_price(0.018).unwrap();
+            assert!(price < evaluator.clone().theoretical_price(0.02).unwrap());
+            assert_approx_eq(Some(evaluator.clone().implied_repo(price).unwrap()), 0.018);
+        }
+        // 交割前有两次期间付息, 按给定再投资利率再投资
+        let evaluator = get_evaluator_without_cp_before_deliver_date();
+        let bond = evaluator.bond.bond.clone();
+        let evaluator = evaluator.update_with_new_info(
+            NaiveDate::from_ymd_opt(2024, 4, 23).unwrap(),
+            ("T2412", 104.745),
+            (bond, 0.0226),
+            0.019,
+            Some(0.015),
+        );
+        let cp = evaluator.clone().with_remain_cp_to_deliver().unwrap();
+        assert!(cp.remain_cp_to_deliver.unwrap() > 0.);
+        let price = evaluator.clone().theoretical_price(0.018).unwrap();
+        assert_approx_eq(Some(evaluator.implied_repo(price).unwrap()), 0.018);
+    }
+
+    #[test]
+    fn test_hedge_contracts() {
+        let evaluator = get_evaluator();
+        // 1亿元面值的债券头寸
+        let hedge = evaluator
+            .clone()
+            .hedge_contracts(1e8, None, Some(0.9))
+            .unwrap();
+        assert_eq!(hedge.contract_size, 1e6);
+        // 以自身为CTD时DV01中性手数等于转换因子比率手数
+        assert!((hedge.dv01 - 95.8).abs() < 1e-10);
+        assert!((hedge.cf - 95.8).abs() < 1e-10);
+        assert!((hedge.beta.unwrap() - 90.).abs() < 1e-10);
+        let ctd = get_basket().remove(0);
+        let hedge = evaluator
+            .clone()
+            .hedge_contracts(-1e8, Some(ctd), None)
+            .unwrap();
+        assert!(hedge.dv01 < 0. && hedge.beta.is_none());
+        let ts = TfEvaluator {
+            future: FuturePrice::new("TS2409", 101.),
+            ..evaluator
+        };
+        assert_eq!(
+            ts.hedge_contracts(1e8, None, None).unwrap().contract_size,
+            2e6
+        );
+
+        let future_prices = [100., 100.2, 100.1, 100.5, f64::NAN, 100.4, 100.3];
+        let bond_prices: Vec<f64> = future_prices.iter().map(|p| 1.2 * p + 3.).collect();
+        let beta = regression_beta(&bond_prices, &future_prices).unwrap();
+        assert!((beta - 1.2).abs() < 1e-10);
+        assert!(regression_beta(&bond_prices[..2], &future_prices[..2]).is_err());
+        assert!(regression_beta(&[1., 2., 3.], &[1., 1., 1.]).is_err());
+    }
+
+    #[test]
+    fn test_calendar_spread() {
+        let near = get_evaluator();
+        let ctd = get_basket().remove(0);
+        let far = TfEvaluator::new(
+            near.date,
+            FuturePrice::new("T2412", 105.6),
+            ctd,
+            near.capital_rate,
+        );
+        let priced = |e: &TfEvaluator, price: f64| TfEvaluator {
+            future: e.future.clone().with_price(price),
+            ..e.clone()
+        };
+        assert!(CalendarSpread::new(far.clone(), near.clone()).is_err());
+        let spread = CalendarSpread::new(near.clone(), far.clone()).unwrap();
+        assert!((spread.spread() + 0.1).abs() < 1e-10);
+        // 两腿均按理论价格定价时, 市场价差等于理论价差
+        let repo = 0.019;
+        let near_price = near.clone().theoretical_price(repo).unwrap();
+        let far_price = far.clone().theoretical_price(repo).unwrap();
+        let spread =
+            CalendarSpread::new(priced(&near, near_price), priced(&far, far_price)).unwrap();
+        assert!((spread.theoretical_spread(repo).unwrap() - spread.spread()).abs() < 1e-10);
+
+        // 两腿使用相同CTD且按同一回购利率定价时, 隐含远期回购利率接近该回购利率
+        let near = TfEvaluator {
+            future: FuturePrice::new("T2409", f64::NAN),
+            ..far.clone()
+        };
+        let near_price = near.clone().theoretical_price(repo).unwrap();
+        let spread =
+            CalendarSpread::new(priced(&near, near_price), priced(&far, far_price)).unwrap();
+        let forward_repo = spread.implied_forward_repo().unwrap();
+        assert!((forward_repo - repo).abs() < 5e-4, "{forward_repo}");
+        // 远月价格越高, 隐含远期回购利率越高
+        let spread =
+            CalendarSpread::new(priced(&near, near_price), priced(&far, far_price + 0.1)).unwrap();
+        assert!(spread.implied_forward_repo().unwrap() > forward_repo);
+    }
+
+    #[test]
+    fn test_roll_window() {
+        let dates: Vec<NaiveDate> = (1..=8)
+            .map(|d| NaiveDate::from_ymd_opt(2024, 8, d).unwrap())
+            .collect();
+        let near_oi = [100., 95., 90., 70., f64::NAN, 40., 20., 10.];
+        let far_oi = [10., 20., 30., 60., 80., 90., 100., 110.];
+        let window = RollWindow::from_open_interest(&dates, &near_oi, &far_oi, 0.3, 0.3)
+            .unwrap()
+            .unwrap();
+        assert_eq!(window.start, dates[2]);
+        assert_eq!(window.crossover, Some(dates[5]));
+        assert_eq!(window.end, Some(dates[6]));
+        let window = RollWindow::from_open_interest(&dates, &near_oi, &far_oi, 0.3, 0.05)
+            .unwrap()
+            .unwrap();
+        assert_eq!(window.end, None);
+        assert!(
+            RollWindow::from_open_interest(&dates[..2], &near_oi[..2], &far_oi[..2], 0.3, 0.3)
+                .unwrap()
+                .is_none()
+        );
+        assert!(RollWindow::from_open_interest(&dates, &near_oi[..2], &far_oi, 0.3, 0.3).is_err());
+    }
+
     #[test]
     fn test_cf_with_cffex() {
         let bond_vec = vec![
@@ -213,4 +528,37 @@ mod tests {
             assert_approx_eq(evaluator.cf, *expect_cf);
         }
     }
+
+    #[test]
+    fn test_early_delivery() {
+        let evaluator = get_evaluator().calc_all().unwrap();
+        let intention_date = NaiveDate::from_ymd_opt(2024, 9, 2).unwrap();
+        let early = evaluator.clone().early_delivery(intention_date).unwrap();
+        assert_eq!(early.deliver_date, NaiveDate::from_ymd_opt(2024, 9, 3));
+        let early = early.calc_all().unwrap();
+        // 转换因子由合约确定, 不随缴款日变化
+        assert_eq!(early.cf, evaluator.cf);
+        assert_eq!(early.remain_days_to_deliver, Some(22));
+        // 提前缴款时交割应计利息和发票价格更低
+        assert!(
+            early.deliver_accrued_interest.unwrap() < evaluator.deliver_accrued_interest.unwrap()
+        );
+        assert!(early.future_dirty_price.unwrap() < evaluator.future_dirty_price.unwrap());
+        assert_approx_eq(
+            Some(early.clone().invoice_amount(10.).unwrap()),
+            early.future_dirty_price.unwrap() * 1e5,
+        );
+        // 重复调用时转换因子保持集中交割的数值
+        let again = early
+            .early_delivery(intention_date)
+            .unwrap()
+            .with_cf()
+            .unwrap();
+        assert_eq!(again.cf, evaluator.cf);
+        assert!(
+            evaluator
+                .early_delivery(intention_date + chrono::Duration::days(16))
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,119 @@
+use super::TfEvaluator;
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+
+/// 国债期货跨期价差分析, 近月和远月合约分别以各自的CTD计算
+#[derive(Debug, Clone)]
+pub struct CalendarSpread {
+    pub near: TfEvaluator, // 近月合约及其CTD
+    pub far: TfEvaluator,  // 远月合约及其CTD
+}
+
+impl CalendarSpread {
+    pub fn new(near: TfEvaluator, far: TfEvaluator) -> Result<Self> {
+        ensure!(
+            near.date == far.date,
+            "Calculating date of near leg {} and far leg {} mismatch",
+            near.date,
+            far.date
+        );
+        let near = near.with_deliver_date()?;
+        let far = far.with_deliver_date()?;
+        ensure!(
+            far.deliver_date > near.deliver_date,
+            "Future {} should deliver later than {}",
+            far.future.code,
+            near.future.code
+        );
+        Ok(Self { near, far })
+    }
+
+    /// 市场跨期价差 = 近月价格 - 远月价格
+    #[inline]
+    pub fn spread(&self) -> f64 {
+        self.near.future.price - self.far.future.price
+    }
+
+    /// 理论跨期价差, 两腿分别按各自CTD在回购利率下的持有成本计算期货理论价格后相减
+    pub fn theoretical_spread(&self, repo_rate: f64) -> Result<f64> {
+        Ok(self.near.clone().theoretical_price(repo_rate)?
+            - self.far.clone().theoretical_price(repo_rate)?)
+    }
+
+    /// 两次交割之间的隐含远期回购利率
+    ///
+    /// 以远月CTD构建: 近月接收交割并支付近月发票价格, 持有至远月交割并收取远月发票价格,
+    /// 期间付息按隐含回购利率再投资, 与IRR的计算口径一致
+    pub fn implied_forward_repo(&self) -> Result<f64> {
+        let far = self.far.clone().with_future_dirty_price()?;
+        // 远月CTD在近月合约下的发票价格
+        let near = TfEvaluator {
+            date: self.near.date,
+            future: self.near.future.clone(),
+            bond: far.bond.clone(),
+            capital_rate: far.capital_rate,
+            reinvest_rate: far.reinvest_rate,
+            ..Default::default()
+        }
+        .with_future_dirty_price()?;
+        far.bond.calc_implied_repo(
+            near.future_dirty_price.unwrap(),
+            self.near.deliver_date.unwrap(),
+            self.far.deliver_date.unwrap(),
+            far.future_dirty_price.unwrap(),
+            None,
+        )
+    }
+}
+
+/// 按持仓量切换判断的移仓窗口
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RollWindow {
+    pub start: NaiveDate,             // 远月持仓量首次达到近月持仓量的start_ratio
+    pub crossover: Option<NaiveDate>, // 远月持仓量首次超过近月持仓量
+    pub end: Option<NaiveDate>,       // 近月持仓量首次降至远月持仓量的end_ratio以下
+}
+
+impl RollWindow {
+    /// 根据近月和远月合约的持仓量序列识别移仓窗口, 远月持仓量从未达到start_ratio时返回None
+    ///
+    /// 例如start_ratio=0.3, end_ratio=0.3表示远月持仓量达到近月的30%时开始移仓,
+    /// 近月持仓量降至远月的30%以下时移仓结束; 缺失的持仓量(NaN)会被跳过
+    pub fn from_open_interest(
+        dates: &[NaiveDate],
+        near_oi: &[f64],
+        far_oi: &[f64],
+        start_ratio: f64,
+        end_ratio: f64,
+    ) -> Result<Option<Self>> {
+        ensure!(
+            dates.len() == near_oi.len() && dates.len() == far_oi.len(),
+            "Length of dates and open interest mismatch"
+        );
+        let mut window: Option<Self> = None;
+        for ((date, near), far) in dates.iter().zip(near_oi).zip(far_oi) {
+            if near.is_nan() || far.is_nan() {
+                continue;
+            }
+            if window.is_none() {
+                if *far < start_ratio * near {
+                    continue;
+                }
+                window = Some(Self {
+                    start: *date,
+                    crossover: None,
+                    end: None,
+                });
+            }
+            let w = window.as_mut().unwrap();
+            if w.crossover.is_none() && far > near {
+                w.crossover = Some(*date);
+            }
+            if *near <= end_ratio * far {
+                w.end = Some(*date);
+                break;
+            }
+        }
+        Ok(window)
+    }
+}
@@ -0,0 +1,137 @@
+use super::{CtdAnalysis, TfEvaluator};
+use crate::bond::BondYtm;
+use crate::future::FuturePrice;
+use anyhow::{Result, ensure};
+use chrono::NaiveDate;
+
+/// 收益率曲线变动情景
+///
+/// 剩余期限为t年的债券收益率变动 = parallel + twist * (t - pivot), twist为正表示曲线变陡
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct YieldShift {
+    pub parallel: f64, // 平移幅度
+    pub twist: f64,    // 每年期限的扭转幅度
+    pub pivot: f64,    // 扭转的支点期限(年)
+}
+
+impl YieldShift {
+    #[inline]
+    pub fn new(parallel: f64, twist: f64, pivot: f64) -> Self {
+        Self {
+            parallel,
+            twist,
+            pivot,
+        }
+    }
+
+    /// 剩余期限为t年的收益率变动
+    #[inline]
+    pub fn shift_at(&self, t: f64) -> f64 {
+        self.parallel + self.twist * (t - self.pivot)
+    }
+
+    /// 平移与扭转幅度的全部组合
+    pub fn grid(parallels: &[f64], twists: &[f64], pivot: f64) -> Vec<Self> {
+        parallels
+            .iter()
+            .flat_map(|p| twists.iter().map(move |t| Self::new(*p, *t, pivot)))
+            .collect()
+    }
+}
+
+/// 单个收益率情景下的CTD分析结果
+#[derive(Debug, Clone)]
+pub struct CtdScenario {
+    pub shift: YieldShift,
+    pub future_price: f64, // 情景下的期货理论价格
+    pub analysis: CtdAnalysis,
+}
+
+impl CtdScenario {
+    /// 情景下的最便宜可交割券
+    #[inline]
+    pub fn ctd(&self) -> &TfEvaluator {
+        self.analysis.ctd()
+    }
+
+    /// 情景下按CTD推断的期货隐含收益率
+    #[inline]
+    pub fn future_ytm(&self) -> Result<f64> {
+        Ok(self
+            .ctd()
+            .clone()
+            .with_future_ytm(true)?
+            .future_ytm
+            .unwrap())
+    }
+}
+
+/// 可交割券的远期净价折算为期货价格, 即(净价-持有收益)/转换因子 = 期货价格 + 净基差/转换因子
+#[inline]
+fn converted_price(evaluator: &TfEvaluator) -> f64 {
+    evaluator.future.price + evaluator.net_basis_spread.unwrap() / evaluator.cf.unwrap()
+}
+
+impl CtdAnalysis {
+    /// CTD切换情景分析
+    ///
+    /// 在每个收益率情景下按剩余期限变动各可交割券收益率并重新定价, 期货理论价格取各券折算远期价格的最小值,
+    /// 并保持当前期货价格相对该最小值的升贴水(交割期权价值)不变, 随后在该期货价格下重新进行CTD分析
+    pub fn scenarios(
+        date: NaiveDate,
+        future: impl Into<FuturePrice>,
+        bonds: impl IntoIterator<Item = BondYtm>,
+        capital_rate: f64,
+        reinvest_rate: Option<f64>,
+        shifts: &[YieldShift],
+    ) -> Result<Vec<CtdScenario>> {
+        let future = future.into();
+        let base = Self::new(date, future.clone(), bonds, capital_rate, reinvest_rate)?;
+        let cheapest = |evaluators: &[TfEvaluator]| {
+            evaluators
+                .iter()
+                .map(converted_price)
+                .fold(f64::INFINITY, f64::min)
+        };
+        let premium = future.price - cheapest(&base.evaluators);
+        shifts
+            .iter()
+            .map(|shift| {
+                let shifted: Vec<BondYtm> = base
+                    .evaluators
+                    .iter()
+                    .map(|e| {
+                        let t = e.bond.remain_year(date);
+                        BondYtm::new(e.bond.bond.clone(), e.bond.ytm() + shift.shift_at(t))
+                    })
+                    .collect();
+                let repriced = Self::new(
+                    date,
+                    future.clone(),
+                    shifted.clone(),
+                    capital_rate,
+                    reinvest_rate,
+                )?;
+                let future_price = cheapest(&repriced.evaluators) + premium;
+                ensure!(
+                    future_price.is_finite(),
+                    "Failed to price future {} under {:?}",
+                    future.code,
+                    shift
+                );
+                let analysis = Self::new(
+                    date,
+                    future.clone().with_price(future_price),
+                    shifted,
+                    capital_rate,
+                    reinvest_rate,
+                )?;
+                Ok(CtdScenario {
+                    shift: *shift,
+                    future_price,
+                    analysis,
+                })
+            })
+            .collect()
+    }
+}
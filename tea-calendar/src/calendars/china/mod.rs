@@ -26,17 +26,17 @@ mod tests {
 
     #[test]
     fn test_sse() {
-        assert_eq!(SSE.is_business_day(date!(2025, 5, 21)), true); // working day
-        assert_eq!(SSE.is_business_day(date!(2025, 5, 2)), false); // holiday
-        assert_eq!(SSE.is_business_day(date!(2025, 5, 17)), false); // weekend
-        assert_eq!(SSE.is_business_day(date!(2025, 4, 27)), false); // working weekend
+        assert!(SSE.is_business_day(date!(2025, 5, 21))); // working day
+        assert!(!SSE.is_business_day(date!(2025, 5, 2))); // holiday
+        assert!(!SSE.is_business_day(date!(2025, 5, 17))); // weekend
+        assert!(!SSE.is_business_day(date!(2025, 4, 27))); // working weekend
     }
 
     #[test]
     fn test_ib() {
-        assert_eq!(IB.is_business_day(date!(2025, 5, 21)), true); // working day
-        assert_eq!(IB.is_business_day(date!(2025, 5, 2)), false); // holiday
-        assert_eq!(IB.is_business_day(date!(2025, 5, 17)), false); // weekend
-        assert_eq!(IB.is_business_day(date!(2025, 4, 27)), true); // working weekend
+        assert!(IB.is_business_day(date!(2025, 5, 21))); // working day
+        assert!(!IB.is_business_day(date!(2025, 5, 2))); // holiday
+        assert!(!IB.is_business_day(date!(2025, 5, 17))); // weekend
+        assert!(IB.is_business_day(date!(2025, 4, 27))); // working weekend
     }
 }